@@ -0,0 +1,237 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::{Bounded, Num, NumCast, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::knapsack::{
+    fractional_bound, items_by_ratio, to_f64s, ConstraintHandling, DimensionsError, RepairMethod,
+};
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Bounds and prices dimensions are not equal: {_0} != {_1} ")]
+struct BoundsDimensionsError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Weight of item {_0} must be positive in unbounded knapsack")]
+struct NonPositiveWeightError(#[error(not(source))] usize);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ItemBounds {
+    /// Maximum number of copies of every item.
+    Bounded(Vec<u32>),
+    /// Any number of copies of every item, limited only by the capacity.
+    Unbounded,
+}
+
+/// Knapsack where every item can be taken multiple times, gene holds the number of taken copies.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BoundedKnapsack<T>
+where
+    T: Num,
+{
+    pub weights: Vec<T>,
+    pub prices: Vec<T>,
+    pub capacity: T,
+    pub bounds: ItemBounds,
+    pub repair_method: RepairMethod,
+    pub constraint_handling: ConstraintHandling,
+}
+
+impl<T> BoundedKnapsack<T>
+where
+    T: Num + NumCast + Clone + std::iter::Sum,
+{
+    pub fn total_weight(&self, chromosome: &[u32]) -> T {
+        sum_counted(chromosome, &self.weights)
+    }
+
+    pub fn total_price(&self, chromosome: &[u32]) -> T {
+        sum_counted(chromosome, &self.prices)
+    }
+}
+
+impl<T> BoundedKnapsack<T>
+where
+    T: Num + Default + std::iter::Sum + PartialOrd + NumCast + Bounded + Clone,
+{
+    /// Adds as many copies of items in given order as fit.
+    fn fill(&self, chromosome: &mut [u32], mut total_weight: T, items: &[usize]) {
+        let max_genes = self.max_genes();
+        for &item in items {
+            let free_capacity = self.capacity.clone() - total_weight.clone();
+            let added = copies_fitting(&free_capacity, &self.weights[item])
+                .min(max_genes[item].saturating_sub(chromosome[item]));
+            chromosome[item] += added;
+            total_weight = total_weight + times(&self.weights[item], added);
+        }
+    }
+}
+
+impl<T> Problem for BoundedKnapsack<T>
+where
+    T: Num + Default + std::iter::Sum + PartialOrd + NumCast + Bounded + Clone,
+{
+    type Fitness = T;
+    type Gene = u32;
+
+    fn chromosome_len(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn max_genes(&self) -> Vec<u32> {
+        match &self.bounds {
+            ItemBounds::Bounded(max_counts) => max_counts.clone(),
+            ItemBounds::Unbounded => self
+                .weights
+                .iter()
+                .map(|weight| copies_fitting(&self.capacity, weight))
+                .collect(),
+        }
+    }
+
+    fn fitness(&self, chromosome: &[u32]) -> T {
+        self.generation_fitness(chromosome, 0)
+    }
+
+    fn generation_fitness(&self, chromosome: &[u32], generation: usize) -> T {
+        let total_weight = self.total_weight(chromosome);
+        if total_weight <= self.capacity {
+            return self.total_price(chromosome);
+        }
+
+        let excess = (total_weight - self.capacity.clone())
+            .to_f64()
+            .unwrap_or(f64::INFINITY);
+        self.constraint_handling
+            .penalize(self.total_price(chromosome), excess, generation)
+    }
+
+    fn is_feasible(&self, chromosome: &[u32]) -> bool {
+        self.total_weight(chromosome) <= self.capacity
+    }
+
+    fn repair(&self, chromosome: &mut [u32]) {
+        self.repair_in_order(chromosome, &items_by_ratio(&self.prices, &self.weights));
+    }
+
+    fn repair_in_order(&self, chromosome: &mut [u32], items: &[usize]) {
+        let mut total_weight = self.total_weight(chromosome);
+
+        for &item in items.iter().rev() {
+            if total_weight <= self.capacity {
+                break;
+            }
+
+            if self.weights[item] <= T::zero() {
+                continue;
+            }
+
+            let excess = total_weight.clone() - self.capacity.clone();
+            let mut removed = copies_fitting(&excess, &self.weights[item]);
+            if times(&self.weights[item], removed) < excess {
+                removed = removed.saturating_add(1);
+            }
+            let removed = removed.min(chromosome[item]);
+            chromosome[item] -= removed;
+            total_weight = total_weight - times(&self.weights[item], removed);
+        }
+
+        if self.repair_method != RepairMethod::DropWorstAndFill {
+            return;
+        }
+
+        self.fill(chromosome, total_weight, items);
+    }
+
+    fn greedy_order(&self) -> Option<Vec<usize>> {
+        Some(items_by_ratio(&self.prices, &self.weights))
+    }
+
+    fn fill_in_order(&self, order: &[usize]) -> Vec<u32> {
+        let mut chromosome = vec![0; self.chromosome_len()];
+        self.fill(&mut chromosome, T::zero(), order);
+        chromosome
+    }
+
+    /// Every item relaxed into one item made of its maximum number of copies that fit.
+    fn upper_bound(&self) -> Option<f64> {
+        let counts: Vec<u32> = self
+            .max_genes()
+            .into_iter()
+            .zip(self.weights.iter())
+            .map(|(count, weight)| count.min(copies_fitting(&self.capacity, weight)))
+            .collect();
+        let scale = |values: &[T]| -> Vec<f64> {
+            to_f64s(values)
+                .iter()
+                .zip(counts.iter())
+                .map(|(value, &count)| value * count as f64)
+                .collect()
+        };
+
+        fractional_bound(
+            &scale(&self.prices),
+            &scale(&self.weights),
+            self.capacity.to_f64()?,
+        )
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.weights.len() != self.prices.len() {
+            return Err(Error::from(DimensionsError(
+                self.weights.len(),
+                self.prices.len(),
+            )));
+        }
+
+        self.constraint_handling.validate()?;
+
+        match &self.bounds {
+            ItemBounds::Bounded(max_counts) if max_counts.len() != self.prices.len() => Err(
+                Error::from(BoundsDimensionsError(max_counts.len(), self.prices.len())),
+            ),
+            ItemBounds::Unbounded => {
+                match self.weights.iter().position(|weight| *weight <= T::zero()) {
+                    Some(item) => Err(Error::from(NonPositiveWeightError(item))),
+                    None => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn sum_counted<T>(chromosome: &[u32], values: &[T]) -> T
+where
+    T: Num + NumCast + Clone + std::iter::Sum,
+{
+    chromosome
+        .iter()
+        .zip(values.iter())
+        .filter(|(&count, _)| count > 0)
+        .map(|(&count, value)| times(value, count))
+        .sum()
+}
+
+fn times<T>(value: &T, count: u32) -> T
+where
+    T: Num + NumCast + Clone,
+{
+    value.clone() * T::from(count).unwrap_or_else(T::zero)
+}
+
+/// Number of copies of item with given weight fitting into capacity.
+fn copies_fitting<T>(capacity: &T, weight: &T) -> u32
+where
+    T: ToPrimitive,
+{
+    let capacity = capacity.to_f64().unwrap_or(0.0);
+    let weight = weight.to_f64().unwrap_or(f64::INFINITY);
+    if weight <= 0.0 {
+        return u32::MAX;
+    }
+
+    (capacity / weight).floor().clamp(0.0, u32::MAX as f64) as u32
+}
@@ -0,0 +1,234 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::{Bounded, Num, NumCast, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::individual::Individual;
+use crate::knapsack::{fill_fractionally, Knapsack};
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Branch and bound needs non-negative weights and capacity")]
+struct NegativeWeightError;
+
+/// Limits of the search, it stops when any of them is reached. `None` searches until optimality is proven.
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BranchAndBoundLimits {
+    pub nodes: Option<usize>,
+    pub time: Option<Duration>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BranchAndBoundResult<T> {
+    pub best_individual: Individual<T>,
+    /// Proven upper bound of the optimal score, equal to the best score when `is_optimal`.
+    pub upper_bound: f64,
+    pub is_optimal: bool,
+    pub nodes: usize,
+    pub elapsed: Duration,
+}
+
+struct Item<T> {
+    index: usize,
+    weight: T,
+    price: T,
+    ratio_weight: f64,
+    ratio_price: f64,
+}
+
+struct Search<'a, T> {
+    items: Vec<Item<T>>,
+    capacity: T,
+    limits: &'a BranchAndBoundLimits,
+    start: Instant,
+    taken: Vec<bool>,
+    best_price: T,
+    best_taken: Vec<bool>,
+    nodes: usize,
+    is_stopped: bool,
+    /// Highest bound of subtrees left unexplored because of the limits.
+    open_bound: f64,
+}
+
+/// Exact optimum of 0/1 knapsack by depth-first branch and bound with the fractional (Dantzig) upper bound.
+/// Unlike `dynamic_programming` memory does not grow with capacity. When a limit stops the search
+/// the result holds the best solution found and an upper bound of the optimum.
+pub fn branch_and_bound<T>(
+    knapsack: &Knapsack<T>,
+    limits: &BranchAndBoundLimits,
+) -> Result<BranchAndBoundResult<T>, Error>
+where
+    T: Num + Copy + Default + PartialOrd + NumCast + Bounded + for<'a> std::iter::Sum<&'a T>,
+{
+    knapsack.validate()?;
+    if knapsack.capacity < T::zero() || knapsack.weights.iter().any(|&weight| weight < T::zero()) {
+        return Err(Error::from(NegativeWeightError));
+    }
+
+    // Items that never fit or do not increase the price are left out of the search
+    let items = knapsack
+        .items_by_ratio()
+        .into_iter()
+        .filter(|&item| {
+            knapsack.weights[item] <= knapsack.capacity && knapsack.prices[item] > T::zero()
+        })
+        .map(|item| Item {
+            index: item,
+            weight: knapsack.weights[item],
+            price: knapsack.prices[item],
+            ratio_weight: knapsack.weights[item].to_f64().unwrap_or(f64::NAN),
+            ratio_price: knapsack.prices[item].to_f64().unwrap_or(f64::NAN),
+        })
+        .collect::<Vec<_>>();
+
+    let mut search = Search {
+        taken: vec![false; items.len()],
+        best_taken: vec![false; items.len()],
+        items,
+        capacity: knapsack.capacity,
+        limits,
+        start: Instant::now(),
+        best_price: T::zero(),
+        nodes: 0,
+        is_stopped: false,
+        open_bound: f64::NEG_INFINITY,
+    };
+    search.fill_greedily();
+    search.explore(0, T::zero(), T::zero());
+
+    let mut chromosome = vec![false; knapsack.weights.len()];
+    for (item, &is_taken) in search.items.iter().zip(search.best_taken.iter()) {
+        chromosome[item.index] = is_taken;
+    }
+
+    let best_score = search.best_price.to_f64().unwrap_or(f64::NAN);
+    Ok(BranchAndBoundResult {
+        best_individual: Individual {
+            fitness_score: knapsack.total_price(&chromosome),
+            chromosome,
+        },
+        upper_bound: best_score.max(search.open_bound),
+        is_optimal: search.open_bound <= best_score,
+        nodes: search.nodes,
+        elapsed: search.start.elapsed(),
+    })
+}
+
+impl<T> Search<'_, T>
+where
+    T: Num + Copy + PartialOrd + ToPrimitive,
+{
+    fn explore(&mut self, depth: usize, weight: T, price: T) {
+        if price > self.best_price {
+            self.best_price = price;
+            self.best_taken.copy_from_slice(&self.taken);
+        }
+
+        if depth == self.items.len() {
+            return;
+        }
+
+        let bound = self.bound(depth, weight, price);
+        if bound <= self.best_price.to_f64().unwrap_or(f64::NAN) {
+            return;
+        }
+
+        if self.is_stopped || self.is_limit_reached() {
+            self.is_stopped = true;
+            self.open_bound = self.open_bound.max(bound);
+            return;
+        }
+        self.nodes += 1;
+
+        let item = &self.items[depth];
+        let (item_weight, item_price) = (item.weight, item.price);
+        if weight + item_weight <= self.capacity {
+            self.taken[depth] = true;
+            self.explore(depth + 1, weight + item_weight, price + item_price);
+            self.taken[depth] = false;
+        }
+
+        self.explore(depth + 1, weight, price);
+    }
+
+    /// Starts from the greedy solution so a search stopped early still returns a good one.
+    fn fill_greedily(&mut self) {
+        let mut weight = T::zero();
+        for (item, is_taken) in self.items.iter().zip(self.best_taken.iter_mut()) {
+            if weight + item.weight <= self.capacity {
+                weight = weight + item.weight;
+                self.best_price = self.best_price + item.price;
+                *is_taken = true;
+            }
+        }
+    }
+
+    /// Price of the knapsack filled greedily with the remaining items, the first one that does not fit is taken fractionally.
+    fn bound(&self, depth: usize, weight: T, price: T) -> f64 {
+        let free_capacity = (self.capacity - weight).to_f64().unwrap_or(f64::NAN);
+        let items = self.items[depth..]
+            .iter()
+            .map(|item| (item.ratio_weight, item.ratio_price));
+
+        price.to_f64().unwrap_or(f64::NAN) + fill_fractionally(items, free_capacity)
+    }
+
+    fn is_limit_reached(&self) -> bool {
+        self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes)
+            || self
+                .limits
+                .time
+                .is_some_and(|time| self.start.elapsed() >= time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_programming::tests::{
+        brute_force, knapsack, p08, random_knapsacks, P08_OPTIMUM,
+    };
+
+    #[test]
+    fn matches_brute_force() {
+        for knapsack in random_knapsacks() {
+            let result = branch_and_bound(&knapsack, &BranchAndBoundLimits::default()).unwrap();
+            assert!(result.is_optimal);
+            assert!(knapsack.is_feasible(&result.best_individual.chromosome));
+            assert_eq!(result.best_individual.fitness_score, brute_force(&knapsack));
+        }
+    }
+
+    #[test]
+    fn p08_optimum() {
+        let result = branch_and_bound(&p08(), &BranchAndBoundLimits::default()).unwrap();
+        assert!(result.is_optimal);
+        assert_eq!(result.best_individual.fitness_score, P08_OPTIMUM);
+        assert_eq!(result.upper_bound, P08_OPTIMUM as f64);
+    }
+
+    #[test]
+    fn non_positive_prices_are_not_taken() {
+        let knapsack = knapsack(vec![1, 2, 3], vec![0, -4, 5], 6);
+        let result = branch_and_bound(&knapsack, &BranchAndBoundLimits::default()).unwrap();
+        assert_eq!(result.best_individual.chromosome, vec![false, false, true]);
+        assert_eq!(result.best_individual.fitness_score, 5);
+    }
+
+    #[test]
+    fn node_limit_keeps_upper_bound() {
+        let limits = BranchAndBoundLimits {
+            nodes: Some(10),
+            time: None,
+        };
+        let result = branch_and_bound(&p08(), &limits).unwrap();
+        assert!(!result.is_optimal);
+        assert!(result.upper_bound >= P08_OPTIMUM as f64);
+        assert!(result.best_individual.fitness_score <= P08_OPTIMUM);
+    }
+}
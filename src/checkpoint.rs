@@ -0,0 +1,235 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+
+use anyhow::Error;
+use derive_more::{Display, Error};
+use log::debug;
+use num_traits::{Num, ToPrimitive};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::crossover_method::Crossover;
+use crate::gene::Gene;
+use crate::genetic_algorithm::{GeneticAlgorithmData, GeneticAlgorithmResultData, RunState};
+use crate::mutation_method::Mutation;
+use crate::observer::GenerationReport;
+use crate::problem::Problem;
+use crate::selection_method::Selection;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Checkpoint interval must be non-zero value")]
+struct CheckpointIntervalError;
+
+pub struct CheckpointSettings {
+    pub path: PathBuf,
+    /// Number of generations between checkpoints, the last generation is always saved.
+    pub interval: usize,
+}
+
+/// Configuration and state of a run between generations.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint<D, S> {
+    data: D,
+    state: S,
+}
+
+/// Runs `genetic_algorithm` saving its configuration and state to `settings.path` every `settings.interval` generations.
+pub fn genetic_algorithm_with_checkpoints<T, G, P, S, C, M>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    settings: &CheckpointSettings,
+) -> Result<GeneticAlgorithmResultData<T, G>, Error>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync + Serialize,
+    G: Gene + Serialize,
+    P: Problem<Fitness = T, Gene = G> + Sync + Serialize,
+    S: Selection<T> + Sync + Serialize,
+    C: Crossover<G> + Sync + Serialize,
+    M: Mutation<G> + Sync + Serialize,
+{
+    validate_settings(settings)?;
+
+    let state = RunState::new(data)?;
+    run(data, state, settings)
+}
+
+/// Continues the run saved in `settings.path`, the continuation is identical to the interrupted run.
+/// Checkpoints keep being saved to the same file.
+pub fn resume_genetic_algorithm<T, G, P, S, C, M>(
+    settings: &CheckpointSettings,
+) -> Result<GeneticAlgorithmResultData<T, G>, Error>
+where
+    T: Num
+        + std::fmt::Debug
+        + Default
+        + PartialOrd
+        + Clone
+        + ToPrimitive
+        + Send
+        + Sync
+        + Serialize
+        + DeserializeOwned,
+    G: Gene + Serialize + DeserializeOwned,
+    P: Problem<Fitness = T, Gene = G> + Sync + Serialize + DeserializeOwned,
+    S: Selection<T> + Sync + Serialize + DeserializeOwned,
+    C: Crossover<G> + Sync + Serialize + DeserializeOwned,
+    M: Mutation<G> + Sync + Serialize + DeserializeOwned,
+{
+    validate_settings(settings)?;
+
+    let reader = BufReader::new(File::open(&settings.path)?);
+    let checkpoint: Checkpoint<GeneticAlgorithmData<P, S, C, M>, RunState<T, G>> =
+        serde_json::from_reader(reader)?;
+    debug!(
+        "Resuming from {} after {} generations",
+        settings.path.display(),
+        checkpoint.state.result.history.len()
+    );
+
+    run(&checkpoint.data, checkpoint.state, settings)
+}
+
+fn validate_settings(settings: &CheckpointSettings) -> Result<(), Error> {
+    if settings.interval == 0 {
+        return Err(Error::from(CheckpointIntervalError));
+    }
+
+    Ok(())
+}
+
+fn run<T, G, P, S, C, M>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    mut state: RunState<T, G>,
+    settings: &CheckpointSettings,
+) -> Result<GeneticAlgorithmResultData<T, G>, Error>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync + Serialize,
+    G: Gene + Serialize,
+    P: Problem<Fitness = T, Gene = G> + Sync + Serialize,
+    S: Selection<T> + Sync + Serialize,
+    C: Crossover<G> + Sync + Serialize,
+    M: Mutation<G> + Sync + Serialize,
+{
+    while !state.finished {
+        state.step(data, &mut |_: &GenerationReport<T, G>| {
+            ControlFlow::Continue(())
+        })?;
+
+        if state.finished || state.result.history.len().is_multiple_of(settings.interval) {
+            save(&settings.path, data, &state)?;
+        }
+    }
+
+    Ok(state.result)
+}
+
+/// Writes the checkpoint to a temporary file first so a crash while saving keeps the previous checkpoint.
+fn save<T, G, P, S, C, M>(
+    path: &Path,
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    state: &RunState<T, G>,
+) -> Result<(), Error>
+where
+    T: Num + Clone + Serialize,
+    G: Serialize,
+    P: Problem<Fitness = T, Gene = G> + Serialize,
+    S: Serialize,
+    C: Serialize,
+    M: Serialize,
+{
+    let temporary_path = path.with_extension("tmp");
+
+    let mut writer = BufWriter::new(File::create(&temporary_path)?);
+    serde_json::to_writer(&mut writer, &Checkpoint { data, state })?;
+    writer.flush()?;
+    drop(writer);
+
+    fs::rename(&temporary_path, path)?;
+    debug!(
+        "Saved checkpoint after {} generations to {}",
+        state.result.history.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::crossover_method::CrossoverMethod;
+    use crate::genetic_algorithm::genetic_algorithm;
+    use crate::knapsack::{ConstraintHandling, Knapsack, RepairMethod};
+    use crate::mutation_method::MutationMethod;
+    use crate::selection_method::SelectionMethod;
+
+    fn data(generations: usize) -> GeneticAlgorithmData<Knapsack<f64>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        GeneticAlgorithmData {
+            problem: Knapsack {
+                weights: (0..30).map(|_| rng.gen_range(1.0..10.0)).collect(),
+                prices: (0..30).map(|_| rng.gen_range(1.0..10.0)).collect(),
+                capacity: 50.0,
+                repair_method: RepairMethod::DropWorst,
+                constraint_handling: ConstraintHandling::DeathPenalty,
+            },
+            population_size: 20,
+            initialization: None,
+            generations,
+            crossover_method: CrossoverMethod::MultiPoint { n_points: 2 },
+            crossover_rate: 0.5,
+            mutation_method: MutationMethod::BitFlip,
+            mutation_rate: 0.1,
+            selection_method: SelectionMethod::Tournament { size: 4 },
+            steady_state: None,
+            repair: None,
+            seed: Some(42),
+            termination: None,
+        }
+    }
+
+    #[test]
+    fn resumed_run_is_identical() {
+        let settings = CheckpointSettings {
+            path: std::env::temp_dir().join(format!(
+                "knapsack_genetic_checkpoint_{}.json",
+                std::process::id()
+            )),
+            interval: 10,
+        };
+        genetic_algorithm_with_checkpoints(&data(20), &settings).unwrap();
+
+        // Extends the finished run as if it was interrupted after 20 of 40 generations
+        let mut checkpoint: serde_json::Value =
+            serde_json::from_reader(File::open(&settings.path).unwrap()).unwrap();
+        checkpoint["data"]["generations"] = 40.into();
+        checkpoint["state"]["finished"] = false.into();
+        serde_json::to_writer(File::create(&settings.path).unwrap(), &checkpoint).unwrap();
+
+        let resumed = resume_genetic_algorithm::<
+            f64,
+            bool,
+            Knapsack<f64>,
+            SelectionMethod,
+            CrossoverMethod,
+            MutationMethod,
+        >(&settings)
+        .unwrap();
+        let uninterrupted = genetic_algorithm(&data(40)).unwrap();
+        fs::remove_file(&settings.path).unwrap();
+
+        assert_eq!(resumed.history, uninterrupted.history);
+        assert_eq!(
+            resumed.best_individual.chromosome,
+            uninterrupted.best_individual.chromosome
+        );
+        assert_eq!(
+            resumed.best_individual.fitness_score,
+            uninterrupted.best_individual.fitness_score
+        );
+    }
+}
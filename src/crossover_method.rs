@@ -1,44 +1,79 @@
-use std::usize;
-
 use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::individual::Individual;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CrossoverMethod {
     SinglePoint,
     MultiPoint { n_points: usize },
     Uniform,
 }
 
-pub fn crossover_method<T>(
-    parents: (&Individual<T>, &Individual<T>),
-    method: &CrossoverMethod,
+pub trait Crossover<G> {
+    fn crossover<T, R>(
+        &self,
+        parents: (&Individual<T, G>, &Individual<T, G>),
+        rng: &mut R,
+    ) -> (Individual<T, G>, Individual<T, G>)
+    where
+        T: Clone + Default,
+        R: Rng + ?Sized;
+}
+
+impl<G> Crossover<G> for CrossoverMethod
+where
+    G: Copy,
+{
+    fn crossover<T, R>(
+        &self,
+        parents: (&Individual<T, G>, &Individual<T, G>),
+        rng: &mut R,
+    ) -> (Individual<T, G>, Individual<T, G>)
+    where
+        T: Clone + Default,
+        R: Rng + ?Sized,
+    {
+        match self {
+            CrossoverMethod::SinglePoint => multi_point_crossover(parents, 1, rng),
+            CrossoverMethod::MultiPoint { n_points } => {
+                multi_point_crossover(parents, *n_points, rng)
+            }
+            CrossoverMethod::Uniform => uniform_crossover(parents, rng),
+        }
+    }
+}
+
+pub fn crossover_method<T, G, C, R>(
+    parents: (&Individual<T, G>, &Individual<T, G>),
+    method: &C,
     crossover_rate: f64,
-) -> (Individual<T>, Individual<T>)
+    rng: &mut R,
+) -> (Individual<T, G>, Individual<T, G>)
 where
     T: Clone + Default,
+    G: Clone,
+    C: Crossover<G> + ?Sized,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
-
     if !rng.gen_bool(crossover_rate) {
         return (parents.0.clone(), parents.1.clone());
     }
 
-    match method {
-        CrossoverMethod::SinglePoint => multi_point_crossover(parents, 1),
-        CrossoverMethod::MultiPoint { n_points } => multi_point_crossover(parents, *n_points),
-        CrossoverMethod::Uniform => uniform_crossover(parents),
-    }
+    method.crossover(parents, rng)
 }
 
-fn multi_point_crossover<T>(
-    parents: (&Individual<T>, &Individual<T>),
+fn multi_point_crossover<T, G, R>(
+    parents: (&Individual<T, G>, &Individual<T, G>),
     n_points: usize,
-) -> (Individual<T>, Individual<T>)
+    rng: &mut R,
+) -> (Individual<T, G>, Individual<T, G>)
 where
     T: Clone,
+    G: Copy,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     let chromosome_len = parents.0.chromosome.len();
     let mut crossover_points: Vec<usize> = (0..n_points)
         .map(|_| rng.gen_range(0..chromosome_len))
@@ -58,11 +93,15 @@ where
     (child1, child2)
 }
 
-fn uniform_crossover<T>(parents: (&Individual<T>, &Individual<T>)) -> (Individual<T>, Individual<T>)
+fn uniform_crossover<T, G, R>(
+    parents: (&Individual<T, G>, &Individual<T, G>),
+    rng: &mut R,
+) -> (Individual<T, G>, Individual<T, G>)
 where
     T: Clone + Default,
+    G: Copy,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     let chromosome_len = parents.0.chromosome.len();
 
     let mut child1 = Individual {
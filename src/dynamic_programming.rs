@@ -0,0 +1,164 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::PrimInt;
+
+use crate::individual::Individual;
+use crate::knapsack::Knapsack;
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(
+    fmt = "Dynamic programming needs non-negative weights and capacity representable as usize"
+)]
+struct WeightRangeError;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Dynamic programming table for {_0} items and capacity {_1} is too large")]
+struct TableSizeError(#[error(not(source))] usize, usize);
+
+/// Exact optimum of 0/1 knapsack with integer weights in O(n * capacity) time and memory.
+/// Returned individual holds the optimal item set and its total price.
+pub fn dynamic_programming<T>(knapsack: &Knapsack<T>) -> Result<Individual<T>, Error>
+where
+    T: PrimInt + Default + for<'a> std::iter::Sum<&'a T>,
+{
+    knapsack.validate()?;
+
+    let capacity = knapsack.capacity.to_usize().ok_or(WeightRangeError)?;
+    let weights = knapsack
+        .weights
+        .iter()
+        .map(|weight| weight.to_usize().ok_or(WeightRangeError))
+        .collect::<Result<Vec<usize>, WeightRangeError>>()?;
+
+    let table_size_error = || TableSizeError(weights.len(), capacity);
+    let columns = capacity.checked_add(1).ok_or_else(table_size_error)?;
+    let cells = weights
+        .len()
+        .checked_mul(columns)
+        .ok_or_else(table_size_error)?;
+
+    // best_prices[c] is the best price of items considered so far with total weight at most c
+    let mut best_prices = table(columns, T::zero()).ok_or_else(table_size_error)?;
+    let mut is_taken = table(cells, false).ok_or_else(table_size_error)?;
+
+    for (item, (&weight, &price)) in weights.iter().zip(knapsack.prices.iter()).enumerate() {
+        if weight > capacity || price <= T::zero() {
+            continue;
+        }
+
+        let row = item * columns;
+        for c in (weight..=capacity).rev() {
+            let taken_price = best_prices[c - weight] + price;
+            if taken_price > best_prices[c] {
+                best_prices[c] = taken_price;
+                is_taken[row + c] = true;
+            }
+        }
+    }
+
+    let mut chromosome = vec![false; weights.len()];
+    let mut c = capacity;
+    for item in (0..weights.len()).rev() {
+        if is_taken[item * columns + c] {
+            chromosome[item] = true;
+            c -= weights[item];
+        }
+    }
+
+    Ok(Individual {
+        fitness_score: knapsack.total_price(&chromosome),
+        chromosome,
+    })
+}
+
+/// Vector of `len` copies of `value`, `None` when it cannot be allocated.
+fn table<V: Clone>(len: usize, value: V) -> Option<Vec<V>> {
+    let mut table = Vec::new();
+    table.try_reserve_exact(len).ok()?;
+    table.resize(len, value);
+    Some(table)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::instance::Instance;
+    use crate::knapsack::{ConstraintHandling, RepairMethod};
+
+    pub(crate) const P08_OPTIMUM: i64 = 13549094;
+
+    pub(crate) fn knapsack(weights: Vec<i64>, prices: Vec<i64>, capacity: i64) -> Knapsack<i64> {
+        Knapsack {
+            weights,
+            prices,
+            capacity,
+            repair_method: RepairMethod::DropWorst,
+            constraint_handling: ConstraintHandling::DeathPenalty,
+        }
+    }
+
+    pub(crate) fn p08() -> Knapsack<i64> {
+        Instance::<i64>::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/data/p08.txt"))
+            .unwrap()
+            .into_knapsack(RepairMethod::DropWorst, ConstraintHandling::DeathPenalty)
+    }
+
+    /// Small instances with zero and negative prices.
+    pub(crate) fn random_knapsacks() -> impl Iterator<Item = Knapsack<i64>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(42);
+        (0..200).map(move |_| {
+            let n_items = rng.gen_range(0..=12);
+            knapsack(
+                (0..n_items).map(|_| rng.gen_range(0..20)).collect(),
+                (0..n_items).map(|_| rng.gen_range(-5..30)).collect(),
+                rng.gen_range(0..60),
+            )
+        })
+    }
+
+    /// Best price over all feasible item sets.
+    pub(crate) fn brute_force(knapsack: &Knapsack<i64>) -> i64 {
+        let n_items = knapsack.weights.len();
+        (0..1u32 << n_items)
+            .map(|set| {
+                (0..n_items)
+                    .map(|item| set & (1 << item) != 0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|chromosome| knapsack.is_feasible(chromosome))
+            .map(|chromosome| knapsack.total_price(&chromosome))
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for knapsack in random_knapsacks() {
+            let solution = dynamic_programming(&knapsack).unwrap();
+            assert!(knapsack.is_feasible(&solution.chromosome));
+            assert_eq!(solution.fitness_score, brute_force(&knapsack));
+        }
+    }
+
+    #[test]
+    fn p08_optimum() {
+        let solution = dynamic_programming(&p08()).unwrap();
+        assert_eq!(solution.fitness_score, P08_OPTIMUM);
+    }
+
+    #[test]
+    fn non_positive_prices_are_not_taken() {
+        let solution = dynamic_programming(&knapsack(vec![1, 2, 3], vec![0, -4, 5], 6)).unwrap();
+        assert_eq!(solution.chromosome, vec![false, false, true]);
+        assert_eq!(solution.fitness_score, 5);
+    }
+
+    #[test]
+    fn too_large_table_is_error() {
+        assert!(dynamic_programming(&knapsack(vec![1, 2], vec![3, 4], i64::MAX)).is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use std::cmp::Ordering;
+
+/// Total order of partially ordered scores (e.g. floats).
+/// Values incomparable with themselves (NaN) are smaller than any other value.
+pub fn total_cmp<T>(a: &T, b: &T) -> Ordering
+where
+    T: PartialOrd,
+{
+    if let Some(ordering) = a.partial_cmp(b) {
+        return ordering;
+    }
+
+    let is_nan = |value: &T| value.partial_cmp(value).is_none();
+    match (is_nan(a), is_nan(b)) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => Ordering::Equal,
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use rand::Rng;
+
+const BOOL_PROBABILITY: f64 = 0.5;
+
+/// Value stored at a chromosome position, `Default` value means the item is not taken.
+pub trait Gene: Copy + Eq + PartialOrd + Hash + Debug + Default + Send + Sync {
+    /// Largest gene value, used as the upper bound when a problem does not limit genes.
+    const MAX: Self;
+
+    /// Random gene from range [default, max].
+    fn random<R>(max: Self, rng: &mut R) -> Self
+    where
+        R: Rng + ?Sized;
+}
+
+impl Gene for bool {
+    const MAX: Self = true;
+
+    fn random<R>(max: Self, rng: &mut R) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        max && rng.gen_bool(BOOL_PROBABILITY)
+    }
+}
+
+macro_rules! impl_integer_gene {
+    ($($gene:ty),*) => {
+        $(
+            impl Gene for $gene {
+                const MAX: Self = <$gene>::MAX;
+
+                fn random<R>(max: Self, rng: &mut R) -> Self
+                where
+                    R: Rng + ?Sized,
+                {
+                    rng.gen_range(0..=max)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_gene!(u8, u16, u32, u64, usize);
@@ -1,23 +1,28 @@
-use rand::distributions::uniform::SampleUniform;
-use std::iter::Sum;
-use std::ops::AddAssign;
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
 
 use anyhow::Error;
 use derive_more::{Display, Error};
 use log::debug;
-use num_traits::Num;
-use rand::Rng;
-
-use crate::crossover_method::{crossover_method, CrossoverMethod};
+use num_traits::{Num, ToPrimitive};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::crossover_method::{crossover_method, Crossover, CrossoverMethod};
+use crate::gene::Gene;
 use crate::individual::Individual;
-use crate::mutation_method::{mutation_method, MutationMethod};
-use crate::selection_method::{select_elites, selection_method, SelectionMethod};
-
-const BOOL_PROBABILITY: f64 = 0.5;
-
-#[derive(Debug, Display, Error)]
-#[display(fmt = "Weights and prices dimensions are not equal: {_0} != {_1} ")]
-struct DimensionsError(#[error(not(source))] usize, usize);
+use crate::initialization::Initialization;
+use crate::mutation_method::{mutation_method, Mutation, MutationMethod};
+use crate::observer::{GenerationReport, Observer};
+use crate::problem::{Problem, RepairMode};
+use crate::selection_method::{select_elites, selection_method, Selection, SelectionMethod};
+use crate::statistics::{calculate_statistics, relative_gap, History};
+use crate::steady_state::SteadyState;
+use crate::termination::{RunProgress, TerminationCriterion, TerminationReason};
 
 #[derive(Debug, Display, Error)]
 #[display(fmt = "The probability of {_0} ({_1}) is not in range of [0 - 1]")]
@@ -27,198 +32,682 @@ struct ProbabilityRangeError(#[error(not(source))] String, f64);
 #[display(fmt = "population_size ({_0}) must be even and non-zero value")]
 struct PopulationSizeError(#[error(not(source))] usize);
 
-pub struct GeneticAlgorithmData<T>
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "P: Serialize, P::Fitness: Serialize, P::Gene: Serialize, S: Serialize, \
+                     C: Serialize, M: Serialize",
+        deserialize = "P: Deserialize<'de>, P::Fitness: Deserialize<'de>, P::Gene: Deserialize<'de>, \
+                       S: Deserialize<'de>, C: Deserialize<'de>, M: Deserialize<'de>"
+    ))
+)]
+pub struct GeneticAlgorithmData<P, S = SelectionMethod, C = CrossoverMethod, M = MutationMethod>
 where
-    T: Num,
+    P: Problem,
 {
-    pub weights: Vec<T>,
-    pub prices: Vec<T>,
-    pub capacity: T,
+    pub problem: P,
     pub population_size: usize,
+    /// Creates the initial population, `None` generates random chromosomes.
+    pub initialization: Option<Initialization<P::Gene>>,
     pub generations: usize,
-    pub crossover_method: CrossoverMethod,
+    pub crossover_method: C,
     pub crossover_rate: f64,
-    pub mutation_method: MutationMethod,
+    pub mutation_method: M,
     pub mutation_rate: f64,
-    pub selection_method: SelectionMethod,
+    pub selection_method: S,
+    /// Replaces only a few individuals per generation instead of the whole population.
+    pub steady_state: Option<SteadyState>,
+    pub repair: Option<RepairMode>,
+    pub seed: Option<u64>,
+    pub termination: Option<TerminationCriterion<P::Fitness>>,
 }
 
 #[derive(Debug)]
-pub struct GeneticAlgorithmResultData<T>
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeneticAlgorithmResultData<T, G = bool>
 where
     T: Num,
 {
-    pub best_individual: Individual<T>,
-    pub score_per_generation: Vec<T>,
-}
-
-pub fn genetic_algorithm<T>(
-    data: &GeneticAlgorithmData<T>,
-) -> Result<GeneticAlgorithmResultData<T>, Error>
-where
-    T: Num
-        + std::fmt::Debug
-        + Default
-        + for<'a> std::iter::Sum<&'a T>
-        + PartialOrd
-        + Ord
-        + Clone
-        + Sum
-        + AddAssign
-        + SampleUniform,
-{
-    validate_data(data)?;
-
-    let data_length = data.weights.len();
-    let mut population = generate_random_population::<T>(data_length, data.population_size);
-    debug!(
-        "Generated population [{}]: {:?}",
-        population.len(),
-        population
-    );
+    /// Best feasible individual, the best infeasible one only when no feasible individual was found.
+    pub best_individual: Individual<T, G>,
+    pub is_best_feasible: bool,
+    pub history: History<T>,
+    pub termination_reason: TerminationReason,
+    /// Upper bound of the best score given by `Problem::upper_bound`.
+    pub upper_bound: Option<f64>,
+}
 
-    let mut result = GeneticAlgorithmResultData {
-        best_individual: find_best_individual(&population),
-        score_per_generation: Vec::new(),
-    };
+impl<T, G> GeneticAlgorithmResultData<T, G>
+where
+    T: Num + ToPrimitive,
+{
+    /// Gap between the best score and `upper_bound` relative to the bound (`0.01` means the best score
+    /// is at most 1% away from the optimum). `None` when the best individual is infeasible.
+    pub fn optimality_gap(&self) -> Option<f64> {
+        if !self.is_best_feasible {
+            return None;
+        }
 
-    result.score_per_generation.reserve(data.population_size);
+        let score = self.best_individual.fitness_score.to_f64()?;
+        Some(relative_gap(score, self.upper_bound?))
+    }
+}
 
-    for _ in 0..data.generations {
-        population
-            .iter_mut()
-            .for_each(|individual| individual.fitness_score = calculate_fitness(data, individual));
+pub fn genetic_algorithm<T, G, P, S, C, M>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+) -> Result<GeneticAlgorithmResultData<T, G>, Error>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+    S: Selection<T> + Sync,
+    C: Crossover<G> + Sync,
+    M: Mutation<G> + Sync,
+{
+    genetic_algorithm_with_observer(data, &mut |_: &GenerationReport<T, G>| {
+        ControlFlow::Continue(())
+    })
+}
 
-        let current_best_individual = find_best_individual(&population);
-        if current_best_individual > result.best_individual {
-            result.best_individual = current_best_individual.clone();
+pub fn genetic_algorithm_with_observer<T, G, P, S, C, M, O>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    observer: &mut O,
+) -> Result<GeneticAlgorithmResultData<T, G>, Error>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+    S: Selection<T> + Sync,
+    C: Crossover<G> + Sync,
+    M: Mutation<G> + Sync,
+    O: Observer<T, G> + ?Sized,
+{
+    let mut state = RunState::new(data)?;
+    while !state.finished {
+        state.step(data, observer)?;
+    }
+
+    Ok(state.result)
+}
+
+/// State of a run between generations, lets other runners (e.g. island model) interleave with the algorithm.
+#[cfg_attr(feature = "checkpoint", derive(Serialize, Deserialize))]
+pub(crate) struct RunState<T, G>
+where
+    T: Num,
+{
+    pub rng: ChaCha8Rng,
+    pub max_genes: Vec<G>,
+    /// `Problem::greedy_order` passed to `Problem::repair_in_order`.
+    pub repair_order: Vec<usize>,
+    pub population: Vec<Individual<T, G>>,
+    pub result: GeneticAlgorithmResultData<T, G>,
+    pub evaluations: usize,
+    pub stagnant_generations: usize,
+    pub elapsed: Duration,
+    pub finished: bool,
+}
+
+impl<T, G> RunState<T, G>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync,
+    G: Gene,
+{
+    pub(crate) fn new<P, S, C, M>(data: &GeneticAlgorithmData<P, S, C, M>) -> Result<Self, Error>
+    where
+        P: Problem<Fitness = T, Gene = G>,
+    {
+        validate_data(data)?;
+
+        let mut rng = create_rng(data.seed);
+
+        let max_genes = data.problem.max_genes();
+        let repair_order = repair_order(&data.problem, data.repair);
+        let population = match &data.initialization {
+            Some(initialization) => initialization.generate_population(
+                &data.problem,
+                &max_genes,
+                data.population_size,
+                &mut rng,
+            )?,
+            None => generate_random_population(&max_genes, data.population_size, &mut rng),
+        };
+        debug!(
+            "Generated population [{}]: {:?}",
+            population.len(),
+            population
+        );
+
+        let mut result = GeneticAlgorithmResultData {
+            best_individual: find_best_individual(&population, |_| false).0,
+            is_best_feasible: false,
+            history: History::default(),
+            termination_reason: TerminationReason::Generations,
+            upper_bound: data.problem.upper_bound(),
+        };
+
+        result.history.generations.reserve(data.generations);
+
+        Ok(RunState {
+            rng,
+            max_genes,
+            repair_order,
+            population,
+            result,
+            evaluations: 0,
+            stagnant_generations: 0,
+            elapsed: Duration::ZERO,
+            finished: data.generations == 0,
+        })
+    }
+
+    /// Runs one generation: breeds a new population (or steady-state offspring) except in the first generation,
+    /// scores it and checks termination.
+    pub(crate) fn step<P, S, C, M, O>(
+        &mut self,
+        data: &GeneticAlgorithmData<P, S, C, M>,
+        observer: &mut O,
+    ) -> Result<(), Error>
+    where
+        P: Problem<Fitness = T, Gene = G> + Sync,
+        S: Selection<T> + Sync,
+        C: Crossover<G> + Sync,
+        M: Mutation<G> + Sync,
+        O: Observer<T, G> + ?Sized,
+    {
+        let start = Instant::now();
+        let generation = self.result.history.len();
+
+        self.evaluations += match (&data.steady_state, generation) {
+            (Some(steady_state), 1..) => self.replace_offspring(data, steady_state, generation)?,
+            _ => {
+                if generation > 0 {
+                    self.population = generate_new_population(
+                        data,
+                        &self.population,
+                        &self.max_genes,
+                        &mut self.rng,
+                    )?;
+                }
+
+                evaluate_population(
+                    &data.problem,
+                    data.repair,
+                    &self.repair_order,
+                    &mut self.population,
+                    generation,
+                );
+                self.population.len()
+            }
+        };
+
+        let feasible_individuals = self
+            .population
+            .iter()
+            .filter(|individual| data.problem.is_feasible(&individual.chromosome))
+            .count();
+
+        // Baldwinian repair scores the repaired chromosome, so every score belongs to a feasible solution
+        let is_feasible = |individual: &Individual<T, G>| {
+            data.repair == Some(RepairMode::Baldwinian)
+                || data.problem.is_feasible(&individual.chromosome)
+        };
+        let (current_best_individual, is_current_best_feasible) =
+            find_best_individual(&self.population, is_feasible);
+
+        // Best individual of the first generation replaces the unscored one of the initial population
+        if generation == 0
+            || (is_current_best_feasible, &current_best_individual)
+                > (self.result.is_best_feasible, &self.result.best_individual)
+        {
+            self.result.best_individual = current_best_individual.clone();
+            self.result.is_best_feasible = is_current_best_feasible;
+            self.stagnant_generations = 0;
+        } else {
+            self.stagnant_generations += 1;
+        }
+
+        let optimality_gap = self.result.optimality_gap();
+        if let Some(gap) = optimality_gap {
+            debug!(
+                "Generation {generation}: best score {:?}, optimality gap {:.4}%",
+                self.result.best_individual.fitness_score,
+                gap * 100.0
+            );
+        }
+        self.result.history.generations.push(calculate_statistics(
+            &self.population,
+            feasible_individuals,
+            self.evaluations,
+            optimality_gap,
+        ));
+
+        self.elapsed += start.elapsed();
+
+        let progress = RunProgress {
+            elapsed: self.elapsed,
+            evaluations: self.evaluations,
+            stagnant_generations: self.stagnant_generations,
+            best_individual: &self.result.best_individual,
+            is_best_feasible: self.result.is_best_feasible,
+            current_best_individual: &current_best_individual,
+            population: &self.population,
+        };
+        let mut termination_reason = data
+            .termination
+            .as_ref()
+            .and_then(|criterion| criterion.check(&progress));
+
+        let report = GenerationReport {
+            generation,
+            population: &self.population,
+            best_individual: &self.result.best_individual,
+            current_best_individual: &current_best_individual,
+            statistics: self.result.history.last().unwrap(),
+        };
+        if observer.on_generation(&report).is_break() && termination_reason.is_none() {
+            termination_reason = Some(TerminationReason::Observer);
         }
 
-        result
-            .score_per_generation
-            .push(current_best_individual.fitness_score);
+        if let Some(reason) = termination_reason {
+            debug!(
+                "Terminated after {} generations: {reason:?}",
+                self.result.history.len()
+            );
+            self.result.termination_reason = reason;
+            self.finished = true;
+        } else if self.result.history.len() >= data.generations {
+            self.finished = true;
+        }
 
-        population = generate_new_population(data, &population)?;
+        Ok(())
     }
 
-    Ok(result)
+    /// Breeds steady-state offspring, scores them and puts them into the population, returns the number of evaluations.
+    fn replace_offspring<P, S, C, M>(
+        &mut self,
+        data: &GeneticAlgorithmData<P, S, C, M>,
+        steady_state: &SteadyState,
+        generation: usize,
+    ) -> Result<usize, Error>
+    where
+        P: Problem<Fitness = T, Gene = G> + Sync,
+        S: Selection<T> + Sync,
+        C: Crossover<G> + Sync,
+        M: Mutation<G> + Sync,
+    {
+        let mut offspring = breed(
+            data,
+            &self.population,
+            &self.max_genes,
+            steady_state.offspring.div_ceil(2),
+            &mut self.rng,
+        )?;
+        offspring.truncate(steady_state.offspring);
+        evaluate_population(
+            &data.problem,
+            data.repair,
+            &self.repair_order,
+            &mut offspring,
+            generation,
+        );
+
+        let evaluations = offspring.len();
+        for child in offspring {
+            let replaced = steady_state.select_replaced(&self.population, &mut self.rng);
+            self.population[replaced] = child;
+        }
+
+        Ok(evaluations)
+    }
 }
 
-fn validate_data<T>(data: &GeneticAlgorithmData<T>) -> Result<(), Error>
+fn validate_data<T, P, S, C, M>(data: &GeneticAlgorithmData<P, S, C, M>) -> Result<(), Error>
 where
-    T: Num,
+    T: PartialOrd,
+    P: Problem<Fitness = T>,
 {
-    if data.weights.len() != data.prices.len() {
-        return Err(Error::from(DimensionsError(
-            data.weights.len(),
-            data.prices.len(),
-        )));
+    data.problem.validate()?;
+    validate_parameters(
+        data.population_size,
+        data.crossover_rate,
+        data.mutation_rate,
+    )?;
+
+    if let Some(initialization) = &data.initialization {
+        initialization.validate(&data.problem, data.population_size)?;
+    }
+
+    if let Some(steady_state) = &data.steady_state {
+        steady_state.validate(data.population_size)?;
     }
 
-    if data.crossover_rate > 1.0 || data.crossover_rate < 0.0 {
+    if let Some(termination) = &data.termination {
+        termination.validate()?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_parameters(
+    population_size: usize,
+    crossover_rate: f64,
+    mutation_rate: f64,
+) -> Result<(), Error> {
+    if !(0.0..=1.0).contains(&crossover_rate) {
         return Err(Error::from(ProbabilityRangeError(
             "crossover_rate".to_string(),
-            data.crossover_rate,
+            crossover_rate,
         )));
     }
 
-    if data.mutation_rate > 1.0 || data.mutation_rate < 0.0 {
+    if !(0.0..=1.0).contains(&mutation_rate) {
         return Err(Error::from(ProbabilityRangeError(
             "mutation_rate".to_string(),
-            data.mutation_rate,
+            mutation_rate,
         )));
     }
 
-    if data.population_size % 2 != 0 || data.population_size == 0 {
-        return Err(Error::from(PopulationSizeError(data.population_size)));
+    if !population_size.is_multiple_of(2) || population_size == 0 {
+        return Err(Error::from(PopulationSizeError(population_size)));
     }
 
     Ok(())
 }
 
-fn generate_random_population<T>(data_length: usize, population_size: usize) -> Vec<Individual<T>>
+pub(crate) fn create_rng(seed: Option<u64>) -> ChaCha8Rng {
+    match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    }
+}
+
+pub(crate) fn generate_random_population<T, G, R>(
+    max_genes: &[G],
+    population_size: usize,
+    rng: &mut R,
+) -> Vec<Individual<T, G>>
 where
-    T: Num + Default,
+    T: Default,
+    G: Gene,
+    R: Rng + ?Sized,
 {
     (0..population_size)
-        .map(|_| generate_random_individual(data_length))
+        .map(|_| generate_random_individual(max_genes, rng))
         .collect()
 }
 
-fn generate_random_individual<T>(data_length: usize) -> Individual<T>
+fn generate_random_individual<T, G, R>(max_genes: &[G], rng: &mut R) -> Individual<T, G>
 where
-    T: Num + Default,
+    T: Default,
+    G: Gene,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     Individual {
-        chromosome: (0..data_length)
-            .map(|_| rng.gen_bool(BOOL_PROBABILITY))
+        chromosome: max_genes
+            .iter()
+            .map(|&max_gene| G::random(max_gene, rng))
             .collect(),
         ..Default::default()
     }
 }
 
-fn calculate_fitness<T>(data: &GeneticAlgorithmData<T>, individual: &Individual<T>) -> T
+/// Gene order used to repair chromosomes, computed once per run.
+pub(crate) fn repair_order<P>(problem: &P, repair: Option<RepairMode>) -> Vec<usize>
 where
-    T: Num + Default + for<'a> std::iter::Sum<&'a T> + std::cmp::PartialOrd,
+    P: Problem,
 {
-    let total_weight: T = individual
-        .chromosome
-        .iter()
-        .zip(data.weights.iter())
-        .filter(|(&is_taken, _)| is_taken)
-        .map(|(_, weight)| weight)
-        .sum();
+    repair
+        .and_then(|_| problem.greedy_order())
+        .unwrap_or_default()
+}
 
-    if total_weight > data.capacity {
-        return T::default();
+/// Scores every individual, in parallel with the `parallel` feature.
+pub(crate) fn evaluate_population<T, G, P>(
+    problem: &P,
+    repair: Option<RepairMode>,
+    repair_order: &[usize],
+    population: &mut [Individual<T, G>],
+    generation: usize,
+) where
+    T: Send,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+{
+    #[cfg(feature = "parallel")]
+    let individuals = population.par_iter_mut();
+    #[cfg(not(feature = "parallel"))]
+    let individuals = population.iter_mut();
+
+    individuals.for_each(|individual| {
+        individual.fitness_score = evaluate(
+            problem,
+            repair,
+            repair_order,
+            &mut individual.chromosome,
+            generation,
+        )
+    });
+}
+
+pub(crate) fn evaluate<P>(
+    problem: &P,
+    repair: Option<RepairMode>,
+    repair_order: &[usize],
+    chromosome: &mut [P::Gene],
+    generation: usize,
+) -> P::Fitness
+where
+    P: Problem,
+{
+    match repair {
+        Some(RepairMode::Lamarckian) => {
+            problem.repair_in_order(chromosome, repair_order);
+            problem.generation_fitness(chromosome, generation)
+        }
+        Some(RepairMode::Baldwinian) => {
+            let mut repaired = chromosome.to_vec();
+            problem.repair_in_order(&mut repaired, repair_order);
+            problem.generation_fitness(&repaired, generation)
+        }
+        None => problem.generation_fitness(chromosome, generation),
     }
+}
 
-    individual
-        .chromosome
+/// Best individual with its feasibility, feasible individuals rank above infeasible ones.
+fn find_best_individual<T, G, F>(
+    population: &[Individual<T, G>],
+    is_feasible: F,
+) -> (Individual<T, G>, bool)
+where
+    T: Num + PartialOrd + Clone,
+    G: Clone,
+    F: Fn(&Individual<T, G>) -> bool,
+{
+    let (is_best_feasible, best_individual) = population
         .iter()
-        .zip(data.prices.iter())
-        .filter(|(&is_taken, _)| is_taken)
-        .map(|(_, price)| price)
-        .sum()
+        .map(|individual| (is_feasible(individual), individual))
+        .max()
+        .unwrap();
+
+    (best_individual.clone(), is_best_feasible)
 }
 
-fn find_best_individual<T>(population: &[Individual<T>]) -> Individual<T>
+fn generate_new_population<T, G, P, S, C, M, R>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    population: &[Individual<T, G>],
+    max_genes: &[G],
+    rng: &mut R,
+) -> Result<Vec<Individual<T, G>>, Error>
 where
-    T: Num + Ord + Clone,
+    T: Num + PartialOrd + Clone + Default + Send + Sync,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+    S: Selection<T> + Sync,
+    C: Crossover<G> + Sync,
+    M: Mutation<G> + Sync,
+    R: Rng + ?Sized,
 {
-    population.iter().max().unwrap().clone()
+    let mut new_population = Vec::with_capacity(data.population_size);
+
+    let n_elites = data.selection_method.n_elites();
+    if n_elites > 0 {
+        new_population.append(&mut select_elites(population, n_elites)?);
+    }
+
+    let n_pairs = data
+        .population_size
+        .saturating_sub(new_population.len())
+        .div_ceil(2);
+    new_population.append(&mut breed(data, population, max_genes, n_pairs, rng)?);
+
+    Ok(new_population)
 }
 
-fn generate_new_population<T>(
-    data: &GeneticAlgorithmData<T>,
-    population: &Vec<Individual<T>>,
-) -> Result<Vec<Individual<T>>, Error>
+/// Every pair of children is bred with its own random number stream derived from a seed drawn from `rng`,
+/// so the children do not depend on the order (or threads) the pairs are bred in.
+fn breed<T, G, P, S, C, M, R>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    population: &[Individual<T, G>],
+    max_genes: &[G],
+    n_pairs: usize,
+    rng: &mut R,
+) -> Result<Vec<Individual<T, G>>, Error>
 where
-    T: Num + Ord + Clone + Sum + AddAssign + SampleUniform + Default,
+    T: Num + PartialOrd + Clone + Default + Send + Sync,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+    S: Selection<T> + Sync,
+    C: Crossover<G> + Sync,
+    M: Mutation<G> + Sync,
+    R: Rng + ?Sized,
 {
-    let mut new_population = Vec::new();
-    new_population.reserve(data.population_size);
+    let seed: u64 = rng.gen();
+
+    #[cfg(feature = "parallel")]
+    let pairs = (0..n_pairs).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let pairs = 0..n_pairs;
+
+    let children = pairs
+        .map(|pair| {
+            let mut pair_rng = ChaCha8Rng::seed_from_u64(seed);
+            pair_rng.set_stream(pair as u64);
+            generate_children(data, population, max_genes, &mut pair_rng)
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(children.into_iter().flatten().collect())
+}
 
-    if let SelectionMethod::Elitism { n_elites, .. } = data.selection_method {
-        new_population.append(&mut select_elites(population, n_elites)?);
+fn generate_children<T, G, P, S, C, M, R>(
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    population: &[Individual<T, G>],
+    max_genes: &[G],
+    rng: &mut R,
+) -> Result<[Individual<T, G>; 2], Error>
+where
+    T: Num + PartialOrd + Clone + Default,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G>,
+    S: Selection<T>,
+    C: Crossover<G>,
+    M: Mutation<G>,
+    R: Rng + ?Sized,
+{
+    let parents = (
+        &selection_method(population, &data.selection_method, rng)?,
+        &selection_method(population, &data.selection_method, rng)?,
+    );
+
+    let children = crossover_method(parents, &data.crossover_method, data.crossover_rate, rng);
+    Ok([
+        mutation_method(
+            &children.0,
+            &data.mutation_method,
+            max_genes,
+            data.mutation_rate,
+            rng,
+        ),
+        mutation_method(
+            &children.1,
+            &data.mutation_method,
+            max_genes,
+            data.mutation_rate,
+            rng,
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dynamic_programming::tests::{p08, P08_OPTIMUM};
+    use crate::knapsack::{ConstraintHandling, Knapsack, PenaltyFunction};
+    use crate::termination::TerminationCriterion;
+
+    fn data(problem: Knapsack<i64>) -> GeneticAlgorithmData<Knapsack<i64>> {
+        GeneticAlgorithmData {
+            problem,
+            population_size: 100,
+            initialization: None,
+            generations: 50,
+            crossover_method: CrossoverMethod::MultiPoint { n_points: 2 },
+            crossover_rate: 0.5,
+            mutation_method: MutationMethod::BitFlip,
+            mutation_rate: 0.1,
+            selection_method: SelectionMethod::Tournament { size: 10 },
+            steady_state: None,
+            repair: None,
+            seed: Some(42),
+            termination: None,
+        }
     }
 
-    while new_population.len() < data.population_size {
-        let parents = (
-            &selection_method(population, &data.selection_method)?,
-            &selection_method(population, &data.selection_method)?,
+    #[test]
+    fn same_seed_gives_same_result() {
+        let data = GeneticAlgorithmData {
+            selection_method: SelectionMethod::Elitism {
+                n_elites: 2,
+                secondary_selection: Box::new(SelectionMethod::Roulette),
+            },
+            repair: Some(RepairMode::Lamarckian),
+            ..data(p08())
+        };
+        let first = genetic_algorithm(&data).unwrap();
+        let second = genetic_algorithm(&data).unwrap();
+
+        assert_eq!(first.history, second.history);
+        assert_eq!(
+            first.best_individual.chromosome,
+            second.best_individual.chromosome
         );
-
-        let children = crossover_method(parents, &data.crossover_method, data.crossover_rate);
-        let children = (
-            mutation_method(&children.0, &data.mutation_method, data.mutation_rate),
-            mutation_method(&children.1, &data.mutation_method, data.mutation_rate),
+        assert_eq!(
+            first.best_individual.fitness_score,
+            second.best_individual.fitness_score
         );
-
-        new_population.push(children.0);
-        new_population.push(children.1);
     }
 
-    Ok(new_population)
+    #[test]
+    fn penalized_best_individual_is_feasible() {
+        let mut problem = p08();
+        problem.constraint_handling = ConstraintHandling::Penalty {
+            function: PenaltyFunction::Linear,
+            coefficient: 1.0,
+        };
+        let data = GeneticAlgorithmData {
+            termination: Some(TerminationCriterion::TargetScore(P08_OPTIMUM)),
+            ..data(problem)
+        };
+        let result = genetic_algorithm(&data).unwrap();
+
+        assert!(result.is_best_feasible);
+        assert!(data.problem.is_feasible(&result.best_individual.chromosome));
+        assert!(result.best_individual.fitness_score <= P08_OPTIMUM);
+        assert!(result.optimality_gap().unwrap() >= 0.0);
+        if result.termination_reason == TerminationReason::TargetScore {
+            assert_eq!(result.best_individual.fitness_score, P08_OPTIMUM);
+        }
+    }
 }
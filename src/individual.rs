@@ -1,40 +1,48 @@
+use std::cmp::Ordering;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::fitness::total_cmp;
+
 #[derive(Clone, Debug)]
-pub struct Individual<T> {
-    pub chromosome: Vec<bool>,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Individual<T, G = bool> {
+    pub chromosome: Vec<G>,
     pub fitness_score: T,
 }
 
-impl<T> PartialEq for Individual<T>
+impl<T, G> PartialEq for Individual<T, G>
 where
-    T: PartialEq,
+    T: PartialOrd,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.fitness_score == other.fitness_score
+        self.cmp(other) == Ordering::Equal
     }
 }
 
-impl<T> Eq for Individual<T> where T: Eq {}
+impl<T, G> Eq for Individual<T, G> where T: PartialOrd {}
 
-impl<T> PartialOrd for Individual<T>
+impl<T, G> PartialOrd for Individual<T, G>
 where
     T: PartialOrd,
 {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.fitness_score.partial_cmp(&other.fitness_score)
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl<T> Ord for Individual<T>
+impl<T, G> Ord for Individual<T, G>
 where
-    T: Ord,
+    T: PartialOrd,
 {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.fitness_score.cmp(&other.fitness_score)
+    fn cmp(&self, other: &Self) -> Ordering {
+        total_cmp(&self.fitness_score, &other.fitness_score)
     }
 }
 
-impl<T: Default> Default for Individual<T> {
-    fn default() -> Individual<T> {
+impl<T: Default, G> Default for Individual<T, G> {
+    fn default() -> Individual<T, G> {
         Individual {
             chromosome: Vec::new(),
             fitness_score: T::default(),
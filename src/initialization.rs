@@ -0,0 +1,232 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::gene::Gene;
+use crate::genetic_algorithm::generate_random_population;
+use crate::individual::Individual;
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Number of seed chromosomes ({_0}) is greater than population_size ({_1})")]
+struct SeedsCountError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Seed chromosome {_0} and chromosome lengths are not equal: {_1} != {_2}")]
+struct SeedLengthError(#[error(not(source))] usize, usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Seed chromosome {_0} has gene {_1} greater than its maximum value")]
+struct SeedGeneError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Share of initialization method ({_0}) must be non-negative value")]
+struct ShareError(#[error(not(source))] f64);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Initialization methods must have positive total share to fill the population")]
+struct NoMethodsError;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Greedy perturbation ({_0}) is not in range of [0 - 1]")]
+struct PerturbationError(#[error(not(source))] f64);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Greedy initialization needs a problem implementing greedy_order")]
+struct GreedyOrderError;
+
+/// Creates the initial population, `seeds` are put into it unchanged and the rest is split between `methods` by their shares.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Initialization<G> {
+    /// Chromosomes of the initial population (e.g. known good solutions).
+    pub seeds: Vec<Vec<G>>,
+    /// Methods with their shares of the rest of the population, `[(Greedy, 1.0), (Random, 3.0)]` creates a quarter greedily.
+    pub methods: Vec<(InitializationMethod, f64)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum InitializationMethod {
+    /// Every gene is random.
+    Random,
+    /// Genes are set in random order by `Problem::fill_in_order` (random items are added until the knapsack is full).
+    RandomFeasible,
+    /// Same as `RandomFeasible` in `Problem::greedy_order` with every position shifted by a random offset
+    /// of up to `perturbation * chromosome length`, so individuals differ.
+    Greedy { perturbation: f64 },
+}
+
+impl<G> Default for Initialization<G> {
+    fn default() -> Initialization<G> {
+        Initialization {
+            seeds: Vec::new(),
+            methods: vec![(InitializationMethod::Random, 1.0)],
+        }
+    }
+}
+
+impl<G> Initialization<G>
+where
+    G: Gene,
+{
+    pub(crate) fn validate<P>(&self, problem: &P, population_size: usize) -> Result<(), Error>
+    where
+        P: Problem<Gene = G>,
+    {
+        if self.seeds.len() > population_size {
+            return Err(Error::from(SeedsCountError(
+                self.seeds.len(),
+                population_size,
+            )));
+        }
+
+        let chromosome_len = problem.chromosome_len();
+        if let Some((seed, chromosome)) = self
+            .seeds
+            .iter()
+            .enumerate()
+            .find(|(_, chromosome)| chromosome.len() != chromosome_len)
+        {
+            return Err(Error::from(SeedLengthError(
+                seed,
+                chromosome.len(),
+                chromosome_len,
+            )));
+        }
+
+        let max_genes = problem.max_genes();
+        for (seed, chromosome) in self.seeds.iter().enumerate() {
+            if let Some(gene) = chromosome
+                .iter()
+                .zip(max_genes.iter())
+                .position(|(gene, max_gene)| gene > max_gene)
+            {
+                return Err(Error::from(SeedGeneError(seed, gene)));
+            }
+        }
+
+        for &(method, share) in &self.methods {
+            if share.is_nan() || share < 0.0 {
+                return Err(Error::from(ShareError(share)));
+            }
+
+            if let InitializationMethod::Greedy { perturbation } = method {
+                if !(0.0..=1.0).contains(&perturbation) {
+                    return Err(Error::from(PerturbationError(perturbation)));
+                }
+            }
+        }
+
+        let total_share: f64 = self.methods.iter().map(|&(_, share)| share).sum();
+        if self.seeds.len() < population_size && total_share <= 0.0 {
+            return Err(Error::from(NoMethodsError));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn generate_population<T, P, R>(
+        &self,
+        problem: &P,
+        max_genes: &[G],
+        population_size: usize,
+        rng: &mut R,
+    ) -> Result<Vec<Individual<T, G>>, Error>
+    where
+        T: Default,
+        P: Problem<Gene = G>,
+        R: Rng + ?Sized,
+    {
+        let mut population: Vec<Individual<T, G>> = self
+            .seeds
+            .iter()
+            .map(|chromosome| individual(chromosome.clone()))
+            .collect();
+
+        // Cumulative rounding makes the counts of all methods add up to the rest of the population
+        let rest = population_size - population.len();
+        let total_share: f64 = self.methods.iter().map(|&(_, share)| share).sum();
+        let mut cumulative_share = 0.0;
+        let mut created = 0;
+
+        for &(method, share) in &self.methods {
+            cumulative_share += share;
+            let target = (rest as f64 * cumulative_share / total_share).round() as usize;
+            let count = target.min(rest) - created;
+            created += count;
+
+            match method {
+                InitializationMethod::Random => {
+                    population.extend(generate_random_population(max_genes, count, rng));
+                }
+                InitializationMethod::RandomFeasible => {
+                    let mut order: Vec<usize> = (0..max_genes.len()).collect();
+                    population.extend((0..count).map(|_| {
+                        order.shuffle(rng);
+                        individual(problem.fill_in_order(&order))
+                    }));
+                }
+                InitializationMethod::Greedy { perturbation } => {
+                    let greedy_order = problem.greedy_order().ok_or(GreedyOrderError)?;
+                    let max_offset = perturbation * greedy_order.len() as f64;
+                    population.extend((0..count).map(|_| {
+                        let keys: Vec<f64> = (0..greedy_order.len())
+                            .map(|position| position as f64 + rng.gen::<f64>() * max_offset)
+                            .collect();
+                        let mut positions: Vec<usize> = (0..greedy_order.len()).collect();
+                        positions.sort_by(|&a, &b| keys[a].total_cmp(&keys[b]));
+
+                        let order: Vec<usize> = positions
+                            .into_iter()
+                            .map(|position| greedy_order[position])
+                            .collect();
+                        individual(problem.fill_in_order(&order))
+                    }));
+                }
+            }
+        }
+
+        Ok(population)
+    }
+}
+
+fn individual<T, G>(chromosome: Vec<G>) -> Individual<T, G>
+where
+    T: Default,
+{
+    Individual {
+        chromosome,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::knapsack::{ConstraintHandling, RepairMethod};
+    use crate::multiple_knapsack::MultipleKnapsack;
+
+    #[test]
+    fn seed_gene_above_maximum_is_error() {
+        let problem = MultipleKnapsack {
+            weights: vec![1, 2, 3],
+            prices: vec![4, 5, 6],
+            capacities: vec![3, 3],
+            repair_method: RepairMethod::DropWorst,
+            constraint_handling: ConstraintHandling::DeathPenalty,
+        };
+        let initialization = |seed: Vec<u32>| Initialization {
+            seeds: vec![seed],
+            ..Default::default()
+        };
+
+        assert!(initialization(vec![2, 1, 0]).validate(&problem, 10).is_ok());
+        assert!(initialization(vec![5, 0, 0])
+            .validate(&problem, 10)
+            .is_err());
+    }
+}
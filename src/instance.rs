@@ -0,0 +1,570 @@
+use std::fmt::Display;
+use std::fs;
+use std::iter::Sum;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{Context, Error};
+use derive_more::{Display, Error};
+use num_traits::Num;
+
+use crate::knapsack::{ConstraintHandling, DimensionsError, Knapsack, RepairMethod};
+use crate::multidimensional_knapsack::MultidimensionalKnapsack;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Invalid instance at line {_0}: {_1}")]
+struct InstanceFormatError(#[error(not(source))] usize, String);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Instance declares {_0} items but contains {_1}")]
+struct ItemsCountError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Solution has {_0} values but instance has {_1} items")]
+struct SolutionLengthError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Solution exceeds capacity of the instance")]
+struct InfeasibleSolutionError;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Price of the solution ({_0}) is not equal to the optimum ({_1})")]
+struct OptimumMismatchError(#[error(not(source))] String, String);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Instance needs at least one constraint")]
+struct NoConstraintsError;
+
+/// 0/1 knapsack instance loaded from a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instance<T> {
+    pub weights: Vec<T>,
+    pub prices: Vec<T>,
+    pub capacity: T,
+    /// Known optimal solution, provided by Burkardt and Pisinger files.
+    pub solution: Option<Vec<bool>>,
+}
+
+/// Multidimensional knapsack instance loaded from a file, `weights` has one row per constraint.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultidimensionalInstance<T> {
+    pub weights: Vec<Vec<T>>,
+    pub prices: Vec<T>,
+    pub capacities: Vec<T>,
+    /// Known optimal score, OR-Library files store zero when it is unknown.
+    pub optimum: Option<T>,
+}
+
+impl<T> Instance<T>
+where
+    T: Num + FromStr + PartialOrd + Clone + Display + for<'a> Sum<&'a T>,
+{
+    /// Reads instance with `n capacity` on the first line followed by `price weight` line for every item.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref();
+        Self::parse(&read(path)?).with_context(|| path.display().to_string())
+    }
+
+    /// Parses instance with `n capacity` on the first line followed by `price weight` line for every item.
+    /// Empty lines are skipped.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let (line_number, header) = lines
+            .next()
+            .ok_or_else(|| InstanceFormatError(1, "missing header".to_string()))?;
+        let [n_items, capacity] =
+            parse_values::<String, 2>(line_number, header, char::is_whitespace)?;
+        let n_items: usize = parse_number(line_number, &n_items)?;
+        let capacity: T = parse_number(line_number, &capacity)?;
+
+        let mut instance = Instance {
+            weights: Vec::with_capacity(n_items),
+            prices: Vec::with_capacity(n_items),
+            capacity,
+            solution: None,
+        };
+        for (line_number, line) in lines {
+            let [price, weight] = parse_values(line_number, line, char::is_whitespace)?;
+            instance.prices.push(price);
+            instance.weights.push(weight);
+        }
+
+        if instance.weights.len() != n_items {
+            return Err(Error::from(ItemsCountError(
+                n_items,
+                instance.weights.len(),
+            )));
+        }
+
+        Ok(instance)
+    }
+
+    /// Reads instance of the Burkardt knapsack_01 dataset stored in `{prefix}_c.txt` (capacity), `{prefix}_w.txt` (weights),
+    /// `{prefix}_p.txt` (profits) and optional `{prefix}_s.txt` (optimal solution) files, e.g. `data/p08`.
+    pub fn from_burkardt(prefix: impl AsRef<Path>) -> Result<Self, Error> {
+        let prefix = prefix.as_ref();
+        let path = |suffix: &str| {
+            let mut path = prefix.as_os_str().to_owned();
+            path.push(suffix);
+            PathBuf::from(path)
+        };
+
+        let solution_path = path("_s.txt");
+        let solution = match solution_path.exists() {
+            true => Some(read(&solution_path)?),
+            false => None,
+        };
+
+        Self::parse_burkardt(
+            &read(&path("_c.txt"))?,
+            &read(&path("_w.txt"))?,
+            &read(&path("_p.txt"))?,
+            solution.as_deref(),
+        )
+        .with_context(|| prefix.display().to_string())
+    }
+
+    /// Parses contents of Burkardt knapsack_01 files, every file holds whitespace separated numbers.
+    pub fn parse_burkardt(
+        capacity: &str,
+        weights: &str,
+        prices: &str,
+        solution: Option<&str>,
+    ) -> Result<Self, Error> {
+        let [capacity] =
+            parse_values(1, capacity.trim(), char::is_whitespace).context("capacity file")?;
+        let weights = parse_tokens(weights).context("weights file")?;
+        let prices = parse_tokens(prices).context("profits file")?;
+        let solution = solution
+            .map(|solution| {
+                parse_tokens::<u8>(solution)
+                    .map(|values| values.into_iter().map(|value| value != 0).collect())
+                    .context("solution file")
+            })
+            .transpose()?;
+
+        let instance = Instance {
+            weights,
+            prices,
+            capacity,
+            solution,
+        };
+        instance.validate()?;
+
+        Ok(instance)
+    }
+
+    /// Reads all instances of a file generated by Pisinger's generator (e.g. `knapPI_1_50_1000.csv`).
+    pub fn from_pisinger(path: impl AsRef<Path>) -> Result<Vec<Self>, Error> {
+        let path = path.as_ref();
+        Self::parse_pisinger(&read(path)?).with_context(|| path.display().to_string())
+    }
+
+    /// Parses instances separated by `-----` lines, every instance has a name line, `n`, `c`, `z` (optimum)
+    /// and `time` lines followed by `index,price,weight,solution` line for every item.
+    pub fn parse_pisinger(text: &str) -> Result<Vec<Self>, Error> {
+        let mut instances = Vec::new();
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .peekable();
+
+        while let Some((name_line, _)) = lines.next() {
+            let mut n_items = None;
+            let mut capacity = None;
+            let mut optimum = None;
+            while let Some(&(line_number, line)) = lines.peek() {
+                if line.contains(',') || line.starts_with("---") {
+                    break;
+                }
+
+                lines.next();
+                let [key, value] =
+                    parse_values::<String, 2>(line_number, line, char::is_whitespace)?;
+                match key.as_str() {
+                    "n" => n_items = Some(parse_number::<usize>(line_number, &value)?),
+                    "c" => capacity = Some(parse_number::<T>(line_number, &value)?),
+                    "z" => optimum = Some(parse_number::<T>(line_number, &value)?),
+                    _ => {}
+                }
+            }
+
+            let missing = |key: &str| InstanceFormatError(name_line, format!("missing \"{key}\""));
+            let n_items = n_items.ok_or_else(|| missing("n"))?;
+            let mut instance = Instance {
+                weights: Vec::with_capacity(n_items),
+                prices: Vec::with_capacity(n_items),
+                capacity: capacity.ok_or_else(|| missing("c"))?,
+                solution: None,
+            };
+            let mut solution = Vec::with_capacity(n_items);
+
+            while let Some(&(line_number, line)) = lines.peek() {
+                lines.next();
+                if line.starts_with("---") {
+                    break;
+                }
+
+                let [_, price, weight, taken] =
+                    parse_values::<String, 4>(line_number, line, |c| c == ',')?;
+                instance.prices.push(parse_number(line_number, &price)?);
+                instance.weights.push(parse_number(line_number, &weight)?);
+                solution.push(parse_number::<u8>(line_number, &taken)? != 0);
+            }
+            instance.solution = Some(solution);
+
+            if instance.weights.len() != n_items {
+                return Err(Error::from(ItemsCountError(
+                    n_items,
+                    instance.weights.len(),
+                )))
+                .with_context(|| format!("instance at line {name_line}"));
+            }
+
+            instance
+                .validate()
+                .with_context(|| format!("instance at line {name_line}"))?;
+            if let (Some(optimum), Some(price)) = (optimum, instance.optimum()) {
+                if optimum != price {
+                    return Err(Error::from(OptimumMismatchError(
+                        price.to_string(),
+                        optimum.to_string(),
+                    )))
+                    .with_context(|| format!("instance at line {name_line}"));
+                }
+            }
+
+            instances.push(instance);
+        }
+
+        Ok(instances)
+    }
+
+    /// Total price of the known optimal solution.
+    pub fn optimum(&self) -> Option<T> {
+        self.solution.as_ref().map(|solution| {
+            solution
+                .iter()
+                .zip(self.prices.iter())
+                .filter(|(&is_taken, _)| is_taken)
+                .map(|(_, price)| price)
+                .sum()
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.weights.len() != self.prices.len() {
+            return Err(Error::from(DimensionsError(
+                self.weights.len(),
+                self.prices.len(),
+            )));
+        }
+
+        if let Some(solution) = &self.solution {
+            if solution.len() != self.weights.len() {
+                return Err(Error::from(SolutionLengthError(
+                    solution.len(),
+                    self.weights.len(),
+                )));
+            }
+
+            let total_weight: T = solution
+                .iter()
+                .zip(self.weights.iter())
+                .filter(|(&is_taken, _)| is_taken)
+                .map(|(_, weight)| weight)
+                .sum();
+            if total_weight > self.capacity {
+                return Err(Error::from(InfeasibleSolutionError));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn into_knapsack(
+        self,
+        repair_method: RepairMethod,
+        constraint_handling: ConstraintHandling,
+    ) -> Knapsack<T> {
+        Knapsack {
+            weights: self.weights,
+            prices: self.prices,
+            capacity: self.capacity,
+            repair_method,
+            constraint_handling,
+        }
+    }
+}
+
+impl<T> MultidimensionalInstance<T>
+where
+    T: Num + FromStr + PartialOrd + Clone,
+{
+    /// Reads all instances of an OR-Library multidimensional knapsack file (`mknap1.txt`, `mknapcb1.txt`, ...).
+    pub fn from_or_library(path: impl AsRef<Path>) -> Result<Vec<Self>, Error> {
+        let path = path.as_ref();
+        Self::parse_or_library(&read(path)?).with_context(|| path.display().to_string())
+    }
+
+    /// Parses whitespace separated numbers: number of instances, then for every instance `n m optimum`,
+    /// `n` prices, `m` rows of `n` weights and `m` capacities.
+    pub fn parse_or_library(text: &str) -> Result<Vec<Self>, Error> {
+        let mut tokens = text
+            .lines()
+            .enumerate()
+            .flat_map(|(index, line)| line.split_whitespace().map(move |token| (index + 1, token)));
+        let mut next = |name: &str| {
+            tokens.next().ok_or_else(|| {
+                Error::from(InstanceFormatError(
+                    text.lines().count(),
+                    format!("unexpected end of file, expected {name}"),
+                ))
+            })
+        };
+
+        let (line_number, token) = next("number of instances")?;
+        let n_instances: usize = parse_number(line_number, token)?;
+
+        let mut instances = Vec::with_capacity(n_instances);
+        for _ in 0..n_instances {
+            let (line_number, token) = next("number of items")?;
+            let n_items: usize = parse_number(line_number, token)?;
+            let (line_number, token) = next("number of constraints")?;
+            let n_constraints: usize = parse_number(line_number, token)?;
+            let (line_number, token) = next("optimum")?;
+            let optimum: T = parse_number(line_number, token)?;
+
+            let mut numbers = |count: usize, name: &str| {
+                (0..count)
+                    .map(|_| {
+                        let (line_number, token) = next(name)?;
+                        parse_number(line_number, token)
+                    })
+                    .collect::<Result<Vec<T>, Error>>()
+            };
+            let prices = numbers(n_items, "price")?;
+            let weights = (0..n_constraints)
+                .map(|_| numbers(n_items, "weight"))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let capacities = numbers(n_constraints, "capacity")?;
+
+            let instance = MultidimensionalInstance {
+                weights,
+                prices,
+                capacities,
+                optimum: (optimum != T::zero()).then_some(optimum),
+            };
+            instance.validate()?;
+            instances.push(instance);
+        }
+
+        Ok(instances)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.capacities.is_empty() {
+            return Err(Error::from(NoConstraintsError));
+        }
+
+        if let Some(row) = self
+            .weights
+            .iter()
+            .find(|row| row.len() != self.prices.len())
+        {
+            return Err(Error::from(DimensionsError(row.len(), self.prices.len())));
+        }
+
+        Ok(())
+    }
+
+    pub fn into_knapsack(
+        self,
+        repair_method: RepairMethod,
+        constraint_handling: ConstraintHandling,
+    ) -> MultidimensionalKnapsack<T> {
+        MultidimensionalKnapsack {
+            weights: self.weights,
+            prices: self.prices,
+            capacities: self.capacities,
+            repair_method,
+            constraint_handling,
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+fn parse_tokens<N>(text: &str) -> Result<Vec<N>, Error>
+where
+    N: FromStr,
+{
+    text.lines()
+        .enumerate()
+        .flat_map(|(index, line)| line.split_whitespace().map(move |token| (index + 1, token)))
+        .map(|(line_number, token)| parse_number(line_number, token))
+        .collect()
+}
+
+fn parse_values<N, const COUNT: usize>(
+    line_number: usize,
+    line: &str,
+    separator: impl Fn(char) -> bool,
+) -> Result<[N; COUNT], Error>
+where
+    N: FromStr,
+{
+    let values: Vec<&str> = line
+        .split(separator)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .collect();
+    if values.len() != COUNT {
+        return Err(Error::from(InstanceFormatError(
+            line_number,
+            format!("expected {COUNT} values, found {}", values.len()),
+        )));
+    }
+
+    let numbers = values
+        .into_iter()
+        .map(|value| parse_number(line_number, value))
+        .collect::<Result<Vec<N>, Error>>()?;
+    Ok(numbers
+        .try_into()
+        .unwrap_or_else(|_| unreachable!("number of values is checked")))
+}
+
+fn parse_number<N>(line_number: usize, value: &str) -> Result<N, Error>
+where
+    N: FromStr,
+{
+    value.parse().map_err(|_| {
+        Error::from(InstanceFormatError(
+            line_number,
+            format!("\"{value}\" is not a valid number"),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PISINGER: &str = "knapPI_1_3_1000_1
+n 3
+c 10
+z 11
+time 0.00
+1,5,4,1
+2,6,5,1
+3,3,7,0
+-----
+";
+
+    const OR_LIBRARY: &str = "1
+3 2 11
+5 6 3
+4 5 7
+1 2 3
+10 5
+";
+
+    fn is_error<E>(result: Result<impl std::fmt::Debug, Error>) -> bool
+    where
+        E: Display + std::fmt::Debug + Send + Sync + 'static,
+    {
+        result.unwrap_err().downcast_ref::<E>().is_some()
+    }
+
+    #[test]
+    fn parses_plain() {
+        let instance = Instance::<i32>::parse("2 10\n5 4\n\n6 5\n").unwrap();
+        assert_eq!(instance.prices, vec![5, 6]);
+        assert_eq!(instance.weights, vec![4, 5]);
+        assert_eq!(instance.capacity, 10);
+        assert_eq!(instance.solution, None);
+    }
+
+    #[test]
+    fn parses_burkardt() {
+        let instance =
+            Instance::<i64>::from_burkardt(concat!(env!("CARGO_MANIFEST_DIR"), "/data/p08"))
+                .unwrap();
+        assert_eq!(instance.weights.len(), 24);
+        assert_eq!(instance.capacity, 6404180);
+        assert_eq!(instance.optimum(), Some(13549094));
+    }
+
+    #[test]
+    fn parses_pisinger() {
+        let instances = Instance::<i32>::parse_pisinger(PISINGER).unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].prices, vec![5, 6, 3]);
+        assert_eq!(instances[0].weights, vec![4, 5, 7]);
+        assert_eq!(instances[0].capacity, 10);
+        assert_eq!(instances[0].optimum(), Some(11));
+    }
+
+    #[test]
+    fn parses_or_library() {
+        let instances = MultidimensionalInstance::<i32>::parse_or_library(OR_LIBRARY).unwrap();
+        assert_eq!(
+            instances,
+            vec![MultidimensionalInstance {
+                weights: vec![vec![4, 5, 7], vec![1, 2, 3]],
+                prices: vec![5, 6, 3],
+                capacities: vec![10, 5],
+                optimum: Some(11),
+            }]
+        );
+    }
+
+    #[test]
+    fn bad_number_is_error() {
+        assert!(is_error::<InstanceFormatError>(Instance::<i32>::parse(
+            "2 10\n5 x\n6 5\n"
+        )));
+        assert!(is_error::<InstanceFormatError>(
+            Instance::<i32>::parse_pisinger(&PISINGER.replace("2,6,5,1", "2,6,five,1"))
+        ));
+    }
+
+    #[test]
+    fn truncated_file_is_error() {
+        assert!(is_error::<InstanceFormatError>(MultidimensionalInstance::<
+            i32,
+        >::parse_or_library(
+            &OR_LIBRARY[..OR_LIBRARY.len() - 3]
+        )));
+        assert!(is_error::<ItemsCountError>(Instance::<i32>::parse(
+            "3 10\n5 4\n6 5\n"
+        )));
+    }
+
+    #[test]
+    fn items_count_mismatch_is_error() {
+        assert!(is_error::<ItemsCountError>(
+            Instance::<i32>::parse_pisinger(&PISINGER.replace("n 3", "n 4"))
+        ));
+        assert!(is_error::<DimensionsError>(
+            Instance::<i32>::parse_burkardt("10", "4 5 7", "5 6", None)
+        ));
+    }
+
+    #[test]
+    fn optimum_mismatch_is_error() {
+        assert!(is_error::<OptimumMismatchError>(
+            Instance::<i32>::parse_pisinger(&PISINGER.replace("z 11", "z 12"))
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+use std::ops::ControlFlow;
+use std::thread;
+
+use anyhow::Error;
+use derive_more::{Display, Error};
+use log::debug;
+use num_traits::{Num, ToPrimitive};
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::crossover_method::{Crossover, CrossoverMethod};
+use crate::gene::Gene;
+use crate::genetic_algorithm::{
+    create_rng, GeneticAlgorithmData, GeneticAlgorithmResultData, RunState,
+};
+use crate::individual::Individual;
+use crate::mutation_method::{Mutation, MutationMethod};
+use crate::observer::GenerationReport;
+use crate::problem::Problem;
+use crate::selection_method::{select_elites, Selection, SelectionMethod};
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Island model needs at least one island")]
+struct NoIslandsError;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "migration_interval must be non-zero value")]
+struct MigrationIntervalError;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Island {_0} receives more migrants than its population size: {_1} > {_2} ")]
+struct MigrationSizeError(#[error(not(source))] usize, usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Topology {
+    /// Island `i` sends migrants to island `i + 1`, the last one to the first one.
+    Ring,
+    /// Every island sends migrants to all other islands.
+    FullyConnected,
+    /// Every island sends migrants to a randomly chosen other island, each island receives migrants from exactly one island.
+    Random,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "P: Serialize, P::Fitness: Serialize, P::Gene: Serialize, S: Serialize, \
+                     C: Serialize, M: Serialize",
+        deserialize = "P: Deserialize<'de>, P::Fitness: Deserialize<'de>, P::Gene: Deserialize<'de>, \
+                       S: Deserialize<'de>, C: Deserialize<'de>, M: Deserialize<'de>"
+    ))
+)]
+pub struct IslandModelData<P, S = SelectionMethod, C = CrossoverMethod, M = MutationMethod>
+where
+    P: Problem,
+{
+    /// Settings of every island, each island runs until its own `generations` or `termination`.
+    pub islands: Vec<GeneticAlgorithmData<P, S, C, M>>,
+    pub topology: Topology,
+    /// Number of generations between migrations.
+    pub migration_interval: usize,
+    /// Number of best individuals sent by an island, they replace the worst individuals of the receiving island.
+    pub migration_size: usize,
+    /// Seed of the random `Topology`, island runs use their own `seed`.
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IslandModelResultData<T, G = bool>
+where
+    T: Num,
+{
+    pub best_individual: Individual<T, G>,
+    pub islands: Vec<GeneticAlgorithmResultData<T, G>>,
+}
+
+pub fn island_model<T, G, P, S, C, M>(
+    data: &IslandModelData<P, S, C, M>,
+) -> Result<IslandModelResultData<T, G>, Error>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+    S: Selection<T> + Sync,
+    C: Crossover<G> + Sync,
+    M: Mutation<G> + Sync,
+{
+    validate_data(data)?;
+
+    let mut rng = create_rng(data.seed);
+    let mut states = data
+        .islands
+        .iter()
+        .map(RunState::new)
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    while states.iter().any(|state| !state.finished) {
+        thread::scope(|scope| {
+            let handles: Vec<_> = states
+                .iter_mut()
+                .zip(data.islands.iter())
+                .map(|(state, island)| {
+                    scope.spawn(move || run_epoch(state, island, data.migration_interval))
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .try_for_each(|handle| handle.join().unwrap())
+        })?;
+
+        migrate(data, &mut states, &mut rng)?;
+    }
+
+    let islands: Vec<_> = states.into_iter().map(|state| state.result).collect();
+    let best_individual = islands
+        .iter()
+        .map(|island| (island.is_best_feasible, &island.best_individual))
+        .max()
+        .unwrap()
+        .1
+        .clone();
+
+    Ok(IslandModelResultData {
+        best_individual,
+        islands,
+    })
+}
+
+fn validate_data<P, S, C, M>(data: &IslandModelData<P, S, C, M>) -> Result<(), Error>
+where
+    P: Problem,
+{
+    if data.islands.is_empty() {
+        return Err(Error::from(NoIslandsError));
+    }
+
+    if data.migration_interval == 0 {
+        return Err(Error::from(MigrationIntervalError));
+    }
+
+    let n_sources = match data.topology {
+        Topology::FullyConnected => data.islands.len() - 1,
+        Topology::Ring | Topology::Random => 1,
+    };
+    for (index, island) in data.islands.iter().enumerate() {
+        let migrants = n_sources * data.migration_size;
+        if migrants > island.population_size {
+            return Err(Error::from(MigrationSizeError(
+                index,
+                migrants,
+                island.population_size,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs up to `generations` generations of one island.
+fn run_epoch<T, G, P, S, C, M>(
+    state: &mut RunState<T, G>,
+    data: &GeneticAlgorithmData<P, S, C, M>,
+    generations: usize,
+) -> Result<(), Error>
+where
+    T: Num + std::fmt::Debug + Default + PartialOrd + Clone + ToPrimitive + Send + Sync,
+    G: Gene,
+    P: Problem<Fitness = T, Gene = G> + Sync,
+    S: Selection<T> + Sync,
+    C: Crossover<G> + Sync,
+    M: Mutation<G> + Sync,
+{
+    for _ in 0..generations {
+        if state.finished {
+            break;
+        }
+
+        state.step(data, &mut |_: &GenerationReport<T, G>| {
+            ControlFlow::Continue(())
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Sends copies of the best individuals of every island to its neighbours, finished islands only send migrants.
+fn migrate<T, G, P, S, C, M, R>(
+    data: &IslandModelData<P, S, C, M>,
+    states: &mut [RunState<T, G>],
+    rng: &mut R,
+) -> Result<(), Error>
+where
+    T: Num + PartialOrd + Clone,
+    G: Clone,
+    P: Problem,
+    R: Rng + ?Sized,
+{
+    let n_islands = states.len();
+    if n_islands < 2 || data.migration_size == 0 {
+        return Ok(());
+    }
+
+    let emigrants = states
+        .iter()
+        .map(|state| select_elites(&state.population, data.migration_size))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Random topology is a ring through the islands in random order, so no island receives migrants twice
+    let mut random_order: Vec<usize> = (0..n_islands).collect();
+    random_order.shuffle(rng);
+    let mut random_targets = vec![0; n_islands];
+    for (position, &source) in random_order.iter().enumerate() {
+        random_targets[source] = random_order[(position + 1) % n_islands];
+    }
+
+    let mut immigrants: Vec<Vec<Individual<T, G>>> = vec![Vec::new(); n_islands];
+    for (source, emigrants) in emigrants.into_iter().enumerate() {
+        let targets: Vec<usize> = match data.topology {
+            Topology::Ring => vec![(source + 1) % n_islands],
+            Topology::FullyConnected => (0..n_islands).filter(|&target| target != source).collect(),
+            Topology::Random => vec![random_targets[source]],
+        };
+
+        for target in targets {
+            immigrants[target].extend(emigrants.iter().cloned());
+        }
+    }
+
+    for (index, (state, immigrants)) in states.iter_mut().zip(immigrants).enumerate() {
+        if state.finished {
+            continue;
+        }
+
+        debug!(
+            "Island {index}: {} immigrants after {} generations",
+            immigrants.len(),
+            state.result.history.len()
+        );
+        state.population.sort();
+        state.population.splice(..immigrants.len(), immigrants);
+    }
+
+    Ok(())
+}
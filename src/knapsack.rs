@@ -0,0 +1,373 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::{Bounded, Num, NumCast, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Weights and prices dimensions are not equal: {_0} != {_1} ")]
+pub(crate) struct DimensionsError(#[error(not(source))] pub(crate) usize, pub(crate) usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Penalty {_0} ({_1}) must be non-negative value")]
+struct PenaltyParameterError(#[error(not(source))] String, f64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RepairMethod {
+    /// Removes taken items with the worst price/weight ratio until the knapsack is not overweight.
+    DropWorst,
+    /// Same as `DropWorst`, then adds items with the best price/weight ratio that still fit.
+    DropWorstAndFill,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PenaltyFunction {
+    Linear,
+    Quadratic,
+    Logarithmic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ConstraintHandling {
+    /// Overweight knapsack scores zero.
+    DeathPenalty,
+    /// Overweight knapsack scores its price reduced by `coefficient * function(excess weight)`.
+    Penalty {
+        function: PenaltyFunction,
+        coefficient: f64,
+    },
+    /// Same as `Penalty` with coefficient growing every generation: `coefficient * (1 + growth * generation)`.
+    AdaptivePenalty {
+        function: PenaltyFunction,
+        coefficient: f64,
+        growth: f64,
+    },
+}
+
+impl PenaltyFunction {
+    pub fn apply(&self, excess: f64) -> f64 {
+        match self {
+            PenaltyFunction::Linear => excess,
+            PenaltyFunction::Quadratic => excess.powi(2),
+            PenaltyFunction::Logarithmic => excess.ln_1p(),
+        }
+    }
+}
+
+impl ConstraintHandling {
+    /// Score of infeasible solution with given price exceeding constraints by `excess`.
+    pub fn penalize<T>(&self, price: T, excess: f64, generation: usize) -> T
+    where
+        T: NumCast + Bounded + Default,
+    {
+        let penalty = match *self {
+            ConstraintHandling::DeathPenalty => return T::default(),
+            ConstraintHandling::Penalty {
+                function,
+                coefficient,
+            } => coefficient * function.apply(excess),
+            ConstraintHandling::AdaptivePenalty {
+                function,
+                coefficient,
+                growth,
+            } => coefficient * (1.0 + growth * generation as f64) * function.apply(excess),
+        };
+
+        // Scores below the range of `T` saturate, so a larger violation never scores higher
+        let price = price.to_f64().unwrap_or(f64::NAN);
+        T::from(price - penalty).unwrap_or_else(T::min_value)
+    }
+
+    /// Negative or NaN parameters would reward overweight solutions.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        let parameters = match *self {
+            ConstraintHandling::DeathPenalty => vec![],
+            ConstraintHandling::Penalty { coefficient, .. } => vec![("coefficient", coefficient)],
+            ConstraintHandling::AdaptivePenalty {
+                coefficient,
+                growth,
+                ..
+            } => vec![("coefficient", coefficient), ("growth", growth)],
+        };
+
+        match parameters
+            .into_iter()
+            .find(|&(_, value)| value.is_nan() || value < 0.0)
+        {
+            Some((name, value)) => Err(Error::from(PenaltyParameterError(name.to_string(), value))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Knapsack<T>
+where
+    T: Num,
+{
+    pub weights: Vec<T>,
+    pub prices: Vec<T>,
+    pub capacity: T,
+    pub repair_method: RepairMethod,
+    pub constraint_handling: ConstraintHandling,
+}
+
+impl<T> Knapsack<T>
+where
+    T: Num + for<'a> std::iter::Sum<&'a T>,
+{
+    pub fn total_weight(&self, chromosome: &[bool]) -> T {
+        sum_taken(chromosome, &self.weights)
+    }
+
+    pub fn total_price(&self, chromosome: &[bool]) -> T {
+        sum_taken(chromosome, &self.prices)
+    }
+}
+
+impl<T> Knapsack<T>
+where
+    T: Num + ToPrimitive + Clone,
+{
+    /// Item indices sorted by price/weight ratio, starting from the best one.
+    pub fn items_by_ratio(&self) -> Vec<usize> {
+        items_by_ratio(&self.prices, &self.weights)
+    }
+}
+
+impl<T> Problem for Knapsack<T>
+where
+    T: Num + Default + for<'a> std::iter::Sum<&'a T> + PartialOrd + NumCast + Bounded + Clone,
+{
+    type Fitness = T;
+    type Gene = bool;
+
+    fn chromosome_len(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn fitness(&self, chromosome: &[bool]) -> T {
+        self.generation_fitness(chromosome, 0)
+    }
+
+    fn generation_fitness(&self, chromosome: &[bool], generation: usize) -> T {
+        let total_weight = self.total_weight(chromosome);
+        if total_weight <= self.capacity {
+            return self.total_price(chromosome);
+        }
+
+        let excess = (total_weight - self.capacity.clone())
+            .to_f64()
+            .unwrap_or(f64::INFINITY);
+        self.constraint_handling
+            .penalize(self.total_price(chromosome), excess, generation)
+    }
+
+    fn is_feasible(&self, chromosome: &[bool]) -> bool {
+        self.total_weight(chromosome) <= self.capacity
+    }
+
+    fn repair(&self, chromosome: &mut [bool]) {
+        self.repair_in_order(chromosome, &self.items_by_ratio());
+    }
+
+    fn repair_in_order(&self, chromosome: &mut [bool], order: &[usize]) {
+        drop_and_fill(
+            chromosome,
+            &self.weights,
+            &self.capacity,
+            order,
+            self.repair_method,
+        );
+    }
+
+    fn greedy_order(&self) -> Option<Vec<usize>> {
+        Some(self.items_by_ratio())
+    }
+
+    fn fill_in_order(&self, order: &[usize]) -> Vec<bool> {
+        let mut chromosome = vec![false; self.chromosome_len()];
+        fill(
+            &mut chromosome,
+            T::zero(),
+            &self.weights,
+            &self.capacity,
+            order,
+        );
+        chromosome
+    }
+
+    fn upper_bound(&self) -> Option<f64> {
+        fractional_bound(
+            &to_f64s(&self.prices),
+            &to_f64s(&self.weights),
+            self.capacity.to_f64()?,
+        )
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.weights.len() != self.prices.len() {
+            return Err(Error::from(DimensionsError(
+                self.weights.len(),
+                self.prices.len(),
+            )));
+        }
+
+        self.constraint_handling.validate()
+    }
+}
+
+pub(crate) fn sum_taken<T>(chromosome: &[bool], values: &[T]) -> T
+where
+    T: Num + for<'a> std::iter::Sum<&'a T>,
+{
+    chromosome
+        .iter()
+        .zip(values.iter())
+        .filter(|(&is_taken, _)| is_taken)
+        .map(|(_, value)| value)
+        .sum()
+}
+
+/// Drops taken items from the end of `items` until the knapsack is not overweight,
+/// with `DropWorstAndFill` then takes items from the start of `items` that still fit.
+pub(crate) fn drop_and_fill<T>(
+    chromosome: &mut [bool],
+    weights: &[T],
+    capacity: &T,
+    items: &[usize],
+    repair_method: RepairMethod,
+) where
+    T: Num + PartialOrd + Clone + for<'a> std::iter::Sum<&'a T>,
+{
+    let mut total_weight = sum_taken(chromosome, weights);
+
+    for &item in items.iter().rev() {
+        if total_weight <= *capacity {
+            break;
+        }
+
+        if chromosome[item] {
+            chromosome[item] = false;
+            total_weight = total_weight - weights[item].clone();
+        }
+    }
+
+    if repair_method != RepairMethod::DropWorstAndFill {
+        return;
+    }
+
+    fill(chromosome, total_weight, weights, capacity, items);
+}
+
+/// Takes items in given order that still fit.
+pub(crate) fn fill<T>(
+    chromosome: &mut [bool],
+    mut total_weight: T,
+    weights: &[T],
+    capacity: &T,
+    items: &[usize],
+) where
+    T: Num + PartialOrd + Clone,
+{
+    for &item in items {
+        let new_weight = total_weight.clone() + weights[item].clone();
+        if !chromosome[item] && new_weight <= *capacity {
+            chromosome[item] = true;
+            total_weight = new_weight;
+        }
+    }
+}
+
+pub(crate) fn items_by_ratio<T>(prices: &[T], weights: &[T]) -> Vec<usize>
+where
+    T: ToPrimitive,
+{
+    let ratios: Vec<f64> = prices
+        .iter()
+        .zip(weights.iter())
+        .map(|(price, weight)| ratio(price, weight))
+        .collect();
+
+    let mut items: Vec<usize> = (0..ratios.len()).collect();
+    items.sort_by(|&a, &b| ratios[b].total_cmp(&ratios[a]));
+    items
+}
+
+pub(crate) fn ratio<T>(price: &T, weight: &T) -> f64
+where
+    T: ToPrimitive,
+{
+    let weight = weight.to_f64().unwrap_or(f64::NAN);
+    if weight == 0.0 {
+        return f64::INFINITY;
+    }
+
+    price.to_f64().unwrap_or(f64::NAN) / weight
+}
+
+pub(crate) fn to_f64s<T>(values: &[T]) -> Vec<f64>
+where
+    T: ToPrimitive,
+{
+    values
+        .iter()
+        .map(|value| value.to_f64().unwrap_or(f64::NAN))
+        .collect()
+}
+
+/// Optimum of the LP relaxation of 0/1 knapsack, `None` when a weight or the capacity is negative.
+pub(crate) fn fractional_bound(prices: &[f64], weights: &[f64], capacity: f64) -> Option<f64> {
+    if capacity < 0.0 || weights.iter().any(|&weight| weight < 0.0) {
+        return None;
+    }
+
+    // Items that never fit or do not increase the price are never part of the optimum
+    let items = items_by_ratio(prices, weights)
+        .into_iter()
+        .filter(|&item| weights[item] <= capacity && prices[item] > 0.0)
+        .map(|item| (weights[item], prices[item]));
+
+    Some(fill_fractionally(items, capacity))
+}
+
+/// Price of the knapsack filled with `(weight, price)` items sorted by ratio, the first one that does not fit
+/// is taken fractionally.
+pub(crate) fn fill_fractionally(items: impl IntoIterator<Item = (f64, f64)>, capacity: f64) -> f64 {
+    let mut free_capacity = capacity;
+    let mut bound = 0.0;
+
+    for (weight, price) in items {
+        if weight > free_capacity {
+            return bound + price * free_capacity / weight;
+        }
+
+        free_capacity -= weight;
+        bound += price;
+    }
+
+    bound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn penalty_saturates_below_range() {
+        let constraint_handling = ConstraintHandling::Penalty {
+            function: PenaltyFunction::Quadratic,
+            coefficient: 1.0,
+        };
+        let small_excess = constraint_handling.penalize(10, 10_000.0, 0);
+        let large_excess = constraint_handling.penalize(10, 60_000.0, 0);
+
+        assert_eq!(small_excess, -99_999_990i32);
+        assert_eq!(large_excess, i32::MIN);
+    }
+}
@@ -1,6 +1,26 @@
+pub mod bounded_knapsack;
+pub mod branch_and_bound;
+#[cfg(feature = "checkpoint")]
+pub mod checkpoint;
 pub mod crossover_method;
+pub mod dynamic_programming;
+pub mod fitness;
+pub mod gene;
 pub mod genetic_algorithm;
 pub mod individual;
+pub mod initialization;
+pub mod instance;
+pub mod island_model;
+pub mod knapsack;
+pub mod multi_objective_knapsack;
+pub mod multidimensional_knapsack;
+pub mod multiple_knapsack;
 pub mod mutation_method;
+pub mod nsga2;
+pub mod observer;
+pub mod problem;
 pub mod selection_method;
+pub mod statistics;
+pub mod steady_state;
+pub mod termination;
 pub mod utils;
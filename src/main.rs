@@ -1,65 +1,341 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use anyhow::Error;
+use clap::{Parser, ValueEnum};
+use derive_more::{Display, Error};
+use knapsack_genetic::branch_and_bound::{branch_and_bound, BranchAndBoundLimits};
 use knapsack_genetic::crossover_method::CrossoverMethod;
+use knapsack_genetic::dynamic_programming::dynamic_programming;
 use knapsack_genetic::genetic_algorithm::{genetic_algorithm, GeneticAlgorithmData};
+use knapsack_genetic::initialization::{Initialization, InitializationMethod};
+use knapsack_genetic::instance::{Instance, MultidimensionalInstance};
+use knapsack_genetic::knapsack::{ConstraintHandling, Knapsack, RepairMethod};
 use knapsack_genetic::mutation_method::MutationMethod;
+use knapsack_genetic::problem::{Problem, RepairMode};
 use knapsack_genetic::selection_method::SelectionMethod;
-
+use knapsack_genetic::termination::TerminationCriterion;
 use knapsack_genetic::utils::{plot_graph, GraphData};
-use log::{error, info};
-
-// Data from (P08) https://people.sc.fsu.edu/~jburkardt/datasets/knapsack_01/knapsack_01.html
-const WEIGHTS: &[i32] = &[
-    382745, 799601, 909247, 729069, 467902, 44328, 34610, 698150, 823460, 903959, 853665, 551830,
-    610856, 670702, 488960, 951111, 323046, 446298, 931161, 31385, 496951, 264724, 224916, 169684,
-];
-const PRICES: &[i32] = &[
-    825594, 1677009, 1676628, 1523970, 943972, 97426, 69666, 1296457, 1679693, 1902996, 1844992,
-    1049289, 1252836, 1319836, 953277, 2067538, 675367, 853655, 1826027, 65731, 901489, 577243,
-    466257, 369261,
-];
-const CAPACITY: i32 = 6404180;
-const OPTIMAL: i32 = 13549094;
-
-const POPULATION_SIZE: usize = 100;
-const GENERATIONS: usize = 1000;
-const CROSSOVER_METHOD: CrossoverMethod = CrossoverMethod::MultiPoint { n_points: 2 };
-const CROSSOVER_RATE: f64 = 0.5;
-const MUTATATION_METHOD: MutationMethod = MutationMethod::BitFlip;
-const MUTATION_RATE: f64 = 0.1;
+use log::{error, info, warn, LevelFilter};
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Instance index {_0} is out of range, the file has {_1} instances")]
+struct InstanceIndexError(#[error(not(source))] usize, usize);
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// `n capacity` on the first line followed by `price weight` line for every item.
+    Plain,
+    /// Burkardt knapsack_01 files, the instance path is the common prefix (e.g. `data/p08`).
+    Burkardt,
+    /// Pisinger's generated instances (e.g. `knapPI_1_50_1000.csv`).
+    Pisinger,
+    /// OR-Library multidimensional knapsack files (e.g. `mknap1.txt`).
+    OrLibrary,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExactSolver {
+    /// Memory grows with capacity.
+    DynamicProgramming,
+    /// Stops at `--exact-node-limit` or `--exact-time-limit`, suited for large capacities.
+    BranchAndBound,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Selection {
+    Tournament,
+    Roulette,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Crossover {
+    SinglePoint,
+    MultiPoint,
+    Uniform,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mutation {
+    BitFlip,
+    Swap,
+    Inversion,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Repair {
+    None,
+    Lamarckian,
+    Baldwinian,
+}
+
+/// Solves 0/1 knapsack problem with genetic algorithm.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Instance file.
+    instance: PathBuf,
+    #[arg(long, value_enum, default_value_t = Format::Plain)]
+    format: Format,
+    /// Index of the instance in files with multiple instances (Pisinger, OR-Library).
+    #[arg(long, default_value_t = 0)]
+    instance_index: usize,
+
+    #[arg(long, default_value_t = 100)]
+    population_size: usize,
+    #[arg(long, default_value_t = 1000)]
+    generations: usize,
+    /// Share of the initial population created by adding random items until the knapsack is full.
+    #[arg(long, default_value_t = 0.0)]
+    feasible_share: f64,
+    /// Share of the initial population created greedily by price/weight ratio, the rest is random.
+    #[arg(long, default_value_t = 0.0)]
+    greedy_share: f64,
+    /// Perturbation of the greedy order in range [0, 1].
+    #[arg(long, default_value_t = 0.1)]
+    greedy_perturbation: f64,
+
+    #[arg(long, value_enum, default_value_t = Selection::Tournament)]
+    selection: Selection,
+    #[arg(long, default_value_t = 10)]
+    tournament_size: usize,
+    /// Number of best individuals copied unchanged to the next generation.
+    #[arg(long, default_value_t = 1)]
+    elites: usize,
+
+    #[arg(long, value_enum, default_value_t = Crossover::MultiPoint)]
+    crossover: Crossover,
+    /// Number of points of multi-point crossover.
+    #[arg(long, default_value_t = 2)]
+    crossover_points: usize,
+    #[arg(long, default_value_t = 0.5)]
+    crossover_rate: f64,
+
+    #[arg(long, value_enum, default_value_t = Mutation::BitFlip)]
+    mutation: Mutation,
+    #[arg(long, default_value_t = 0.1)]
+    mutation_rate: f64,
+
+    #[arg(long, value_enum, default_value_t = Repair::Lamarckian)]
+    repair: Repair,
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Known optimal score, stops the run when reached and is drawn in the plot. Defaults to the optimum stored in the instance.
+    #[arg(long)]
+    optimal: Option<i64>,
+    /// Computes the optimum with an exact solver when it is not known, branch and bound by default.
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "branch-and-bound")]
+    exact: Option<ExactSolver>,
+    /// Maximum number of explored nodes of branch and bound.
+    #[arg(long)]
+    exact_node_limit: Option<usize>,
+    /// Time limit of branch and bound in seconds.
+    #[arg(long)]
+    exact_time_limit: Option<u64>,
+
+    #[arg(long, default_value = "out.png")]
+    plot: PathBuf,
+    /// Writes the result as JSON to the given file.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Overrides `RUST_LOG` (off, error, warn, info, debug, trace).
+    #[arg(long)]
+    log_level: Option<LevelFilter>,
+}
 
 fn main() {
-    pretty_env_logger::init();
+    let args = Args::parse();
+
+    let mut logger = pretty_env_logger::formatted_builder();
+    match args.log_level {
+        Some(level) => logger.filter_level(level),
+        None => logger.parse_default_env(),
+    };
+    logger.init();
+
+    if let Err(e) = run(&args) {
+        error!("Failed with error: {e}");
+        process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    let repair_method = RepairMethod::DropWorstAndFill;
+    let constraint_handling = ConstraintHandling::DeathPenalty;
+
+    let instance = match args.format {
+        Format::Plain => Instance::<i64>::from_file(&args.instance)?,
+        Format::Burkardt => Instance::from_burkardt(&args.instance)?,
+        Format::Pisinger => select_instance(Instance::from_pisinger(&args.instance)?, args)?,
+        Format::OrLibrary => {
+            let instance = select_instance(
+                MultidimensionalInstance::<i64>::from_or_library(&args.instance)?,
+                args,
+            )?;
+            if args.exact.is_some() {
+                warn!("Exact solver supports only 0/1 knapsack instances");
+            }
+
+            let optimum = instance.optimum;
+            return solve(
+                args,
+                instance.into_knapsack(repair_method, constraint_handling),
+                optimum,
+            );
+        }
+    };
+
+    let mut optimum = instance.optimum();
+    let knapsack = instance.into_knapsack(repair_method, constraint_handling);
+    if optimum.is_none() && args.optimal.is_none() {
+        if let Some(solver) = args.exact {
+            optimum = exact_optimum(&knapsack, solver, args)?;
+        }
+    }
+
+    solve(args, knapsack, optimum)
+}
+
+fn exact_optimum(
+    knapsack: &Knapsack<i64>,
+    solver: ExactSolver,
+    args: &Args,
+) -> Result<Option<i64>, Error> {
+    match solver {
+        ExactSolver::DynamicProgramming => {
+            let solution = dynamic_programming(knapsack)?;
+            info!("Exact optimum: {:?}", solution);
+            Ok(Some(solution.fitness_score))
+        }
+        ExactSolver::BranchAndBound => {
+            let limits = BranchAndBoundLimits {
+                nodes: args.exact_node_limit,
+                time: args.exact_time_limit.map(Duration::from_secs),
+            };
+            let result = branch_and_bound(knapsack, &limits)?;
+            info!(
+                "Branch and bound explored {} nodes in {:?}, upper bound {}: {:?}",
+                result.nodes, result.elapsed, result.upper_bound, result.best_individual
+            );
+            if !result.is_optimal {
+                warn!("Branch and bound stopped at a limit before proving optimality");
+                return Ok(None);
+            }
+
+            Ok(Some(result.best_individual.fitness_score))
+        }
+    }
+}
+
+fn select_instance<I>(instances: Vec<I>, args: &Args) -> Result<I, Error> {
+    let n_instances = instances.len();
+    instances
+        .into_iter()
+        .nth(args.instance_index)
+        .ok_or_else(|| Error::from(InstanceIndexError(args.instance_index, n_instances)))
+}
+
+fn solve<P>(args: &Args, problem: P, optimum: Option<i64>) -> Result<(), Error>
+where
+    P: Problem<Fitness = i64, Gene = bool> + Sync,
+{
+    let optimal = args.optimal.or(optimum);
+
+    let secondary_selection = match args.selection {
+        Selection::Tournament => SelectionMethod::Tournament {
+            size: args.tournament_size,
+        },
+        Selection::Roulette => SelectionMethod::Roulette,
+    };
+    let selection_method = match args.elites {
+        0 => secondary_selection,
+        n_elites => SelectionMethod::Elitism {
+            n_elites,
+            secondary_selection: Box::new(secondary_selection),
+        },
+    };
+
+    let initialization = (args.feasible_share > 0.0 || args.greedy_share > 0.0).then(|| {
+        let random_share = (1.0 - args.feasible_share - args.greedy_share).max(0.0);
+        Initialization {
+            seeds: Vec::new(),
+            methods: vec![
+                (InitializationMethod::RandomFeasible, args.feasible_share),
+                (
+                    InitializationMethod::Greedy {
+                        perturbation: args.greedy_perturbation,
+                    },
+                    args.greedy_share,
+                ),
+                (InitializationMethod::Random, random_share),
+            ],
+        }
+    });
 
     let data = GeneticAlgorithmData {
-        weights: WEIGHTS.to_vec(),
-        prices: PRICES.to_vec(),
-        capacity: CAPACITY,
-        population_size: POPULATION_SIZE,
-        generations: GENERATIONS,
-        crossover_method: CROSSOVER_METHOD,
-        crossover_rate: CROSSOVER_RATE,
-        mutation_method: MUTATATION_METHOD,
-        mutation_rate: MUTATION_RATE,
-        selection_method: SelectionMethod::Elitism {
-            n_elites: 1,
-            secondary_selection: Box::new(SelectionMethod::Tournament { size: 10 }),
+        problem,
+        population_size: args.population_size,
+        initialization,
+        generations: args.generations,
+        crossover_method: match args.crossover {
+            Crossover::SinglePoint => CrossoverMethod::SinglePoint,
+            Crossover::MultiPoint => CrossoverMethod::MultiPoint {
+                n_points: args.crossover_points,
+            },
+            Crossover::Uniform => CrossoverMethod::Uniform,
+        },
+        crossover_rate: args.crossover_rate,
+        mutation_method: match args.mutation {
+            Mutation::BitFlip => MutationMethod::BitFlip,
+            Mutation::Swap => MutationMethod::Swap,
+            Mutation::Inversion => MutationMethod::Inversion,
         },
+        mutation_rate: args.mutation_rate,
+        selection_method,
+        steady_state: None,
+        repair: match args.repair {
+            Repair::None => None,
+            Repair::Lamarckian => Some(RepairMode::Lamarckian),
+            Repair::Baldwinian => Some(RepairMode::Baldwinian),
+        },
+        seed: args.seed,
+        termination: optimal.map(TerminationCriterion::TargetScore),
     };
 
+    let result = genetic_algorithm(&data)?;
+    info!(
+        "Stopped after {} generations: {:?}",
+        result.history.len(),
+        result.termination_reason
+    );
+    info!("Best chromosome: {:?}", &result.best_individual);
+    if let (Some(upper_bound), Some(gap)) = (result.upper_bound, result.optimality_gap()) {
+        info!(
+            "Upper bound: {upper_bound}, optimality gap: {:.4}%",
+            gap * 100.0
+        );
+    }
+
+    if let Some(output) = &args.output {
+        let mut writer = BufWriter::new(File::create(output)?);
+        serde_json::to_writer_pretty(&mut writer, &result)?;
+        writer.flush()?;
+    }
+
+    let plot = args.plot.to_string_lossy();
     let graph_data = GraphData {
+        out_file: &plot,
         y_label_area_size: 60,
-        y_max_value: Some(OPTIMAL as f32 * 1.2),
-        optimal_value_line: Some(OPTIMAL as f32),
+        y_max_value: optimal.map(|optimal| optimal as f32 * 1.2),
+        optimal_value_line: optimal.map(|optimal| optimal as f32),
         ..Default::default()
     };
-
-    match genetic_algorithm(&data) {
-        Ok(result) => {
-            info!("Best chromosome: {:?}", &result.best_individual);
-            if let Err(e) = plot_graph(&result, &graph_data) {
-                error!("Failed to plot with error: {e}");
-            }
-        }
-
-        Err(e) => error!("Genetic algorithm failed with error: {e}"),
+    if let Err(e) = plot_graph(&result, &graph_data) {
+        error!("Failed to plot with error: {e}");
     }
+
+    Ok(())
 }
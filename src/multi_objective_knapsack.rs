@@ -0,0 +1,140 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::{Num, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::knapsack::{drop_and_fill, items_by_ratio, sum_taken, RepairMethod};
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Values row {_0} and weights dimensions are not equal: {_1} != {_2} ")]
+struct RowDimensionsError(#[error(not(source))] usize, usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Multi-objective knapsack needs at least one objective")]
+struct NoObjectivesError;
+
+/// 0/1 knapsack maximizing every row of `values` (e.g. price and a second value column) at once.
+/// With `minimize_weight` the unused capacity is one more objective. Overweight knapsacks score zero in all objectives.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiObjectiveKnapsack<T>
+where
+    T: Num,
+{
+    pub weights: Vec<T>,
+    pub values: Vec<Vec<T>>,
+    pub capacity: T,
+    pub minimize_weight: bool,
+    pub repair_method: RepairMethod,
+}
+
+impl<T> MultiObjectiveKnapsack<T>
+where
+    T: Num + for<'a> std::iter::Sum<&'a T>,
+{
+    pub fn total_weight(&self, chromosome: &[bool]) -> T {
+        sum_taken(chromosome, &self.weights)
+    }
+
+    pub fn total_values(&self, chromosome: &[bool]) -> Vec<T> {
+        self.values
+            .iter()
+            .map(|row| sum_taken(chromosome, row))
+            .collect()
+    }
+}
+
+impl<T> MultiObjectiveKnapsack<T>
+where
+    T: Num + ToPrimitive + Clone,
+{
+    /// Item indices sorted by ratio of summed values to weight, starting from the best one.
+    pub fn items_by_ratio(&self) -> Vec<usize> {
+        let summed_values: Vec<f64> = (0..self.weights.len())
+            .map(|item| {
+                self.values
+                    .iter()
+                    .map(|row| row[item].to_f64().unwrap_or(f64::NAN))
+                    .sum()
+            })
+            .collect();
+        let weights: Vec<f64> = self
+            .weights
+            .iter()
+            .map(|weight| weight.to_f64().unwrap_or(f64::NAN))
+            .collect();
+
+        items_by_ratio(&summed_values, &weights)
+    }
+
+    fn n_objectives(&self) -> usize {
+        self.values.len() + usize::from(self.minimize_weight)
+    }
+}
+
+impl<T> Problem for MultiObjectiveKnapsack<T>
+where
+    T: Num + Default + for<'a> std::iter::Sum<&'a T> + PartialOrd + ToPrimitive + Clone,
+{
+    type Fitness = Vec<T>;
+    type Gene = bool;
+
+    fn chromosome_len(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn fitness(&self, chromosome: &[bool]) -> Vec<T> {
+        let total_weight = self.total_weight(chromosome);
+        if total_weight > self.capacity {
+            return vec![T::default(); self.n_objectives()];
+        }
+
+        let mut objectives = self.total_values(chromosome);
+        if self.minimize_weight {
+            objectives.push(self.capacity.clone() - total_weight);
+        }
+
+        objectives
+    }
+
+    fn is_feasible(&self, chromosome: &[bool]) -> bool {
+        self.total_weight(chromosome) <= self.capacity
+    }
+
+    fn repair(&self, chromosome: &mut [bool]) {
+        self.repair_in_order(chromosome, &self.items_by_ratio());
+    }
+
+    fn repair_in_order(&self, chromosome: &mut [bool], order: &[usize]) {
+        drop_and_fill(
+            chromosome,
+            &self.weights,
+            &self.capacity,
+            order,
+            self.repair_method,
+        );
+    }
+
+    fn greedy_order(&self) -> Option<Vec<usize>> {
+        Some(self.items_by_ratio())
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.n_objectives() == 0 {
+            return Err(Error::from(NoObjectivesError));
+        }
+
+        for (row, values) in self.values.iter().enumerate() {
+            if values.len() != self.weights.len() {
+                return Err(Error::from(RowDimensionsError(
+                    row,
+                    values.len(),
+                    self.weights.len(),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::{Bounded, Num, NumCast, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::knapsack::{
+    fractional_bound, ratio, sum_taken, to_f64s, ConstraintHandling, RepairMethod,
+};
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Number of weight rows and capacities are not equal: {_0} != {_1} ")]
+struct ConstraintsError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Weights row {_0} and prices dimensions are not equal: {_1} != {_2} ")]
+struct RowDimensionsError(#[error(not(source))] usize, usize, usize);
+
+/// Knapsack with multiple constraints (e.g. weight, volume and cost), `weights[k][i]` is
+/// the amount of resource `k` used by item `i` and `capacities[k]` is the limit of resource `k`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultidimensionalKnapsack<T>
+where
+    T: Num,
+{
+    pub weights: Vec<Vec<T>>,
+    pub prices: Vec<T>,
+    pub capacities: Vec<T>,
+    pub repair_method: RepairMethod,
+    pub constraint_handling: ConstraintHandling,
+}
+
+impl<T> MultidimensionalKnapsack<T>
+where
+    T: Num + for<'a> std::iter::Sum<&'a T>,
+{
+    pub fn total_weights(&self, chromosome: &[bool]) -> Vec<T> {
+        self.weights
+            .iter()
+            .map(|row| sum_taken(chromosome, row))
+            .collect()
+    }
+
+    pub fn total_price(&self, chromosome: &[bool]) -> T {
+        sum_taken(chromosome, &self.prices)
+    }
+}
+
+impl<T> MultidimensionalKnapsack<T>
+where
+    T: Num + ToPrimitive + Clone,
+{
+    /// Item indices sorted by ratio of price to weights relative to capacities, starting from the best one.
+    pub fn items_by_ratio(&self) -> Vec<usize> {
+        let ratios: Vec<f64> = (0..self.prices.len())
+            .map(|item| {
+                let relative_weight: f64 = self
+                    .weights
+                    .iter()
+                    .zip(self.capacities.iter())
+                    .map(|(row, capacity)| ratio(&row[item], capacity))
+                    .sum();
+                self.prices[item].to_f64().unwrap_or(f64::NAN) / relative_weight
+            })
+            .collect();
+
+        let mut items: Vec<usize> = (0..ratios.len()).collect();
+        items.sort_by(|&a, &b| ratios[b].total_cmp(&ratios[a]));
+        items
+    }
+
+    fn excess(&self, total_weights: &[T]) -> f64 {
+        total_weights
+            .iter()
+            .zip(self.capacities.iter())
+            .map(|(weight, capacity)| {
+                let excess = weight.to_f64().unwrap_or(f64::INFINITY)
+                    - capacity.to_f64().unwrap_or(f64::NAN);
+                excess.max(0.0)
+            })
+            .sum()
+    }
+}
+
+impl<T> MultidimensionalKnapsack<T>
+where
+    T: Num + PartialOrd + Clone,
+{
+    /// Takes items in given order that still fit into all capacities.
+    fn fill(&self, chromosome: &mut [bool], mut total_weights: Vec<T>, items: &[usize]) {
+        for &item in items {
+            let new_weights: Vec<T> = total_weights
+                .iter()
+                .zip(self.weights.iter())
+                .map(|(weight, row)| weight.clone() + row[item].clone())
+                .collect();
+            if !chromosome[item] && fits(&new_weights, &self.capacities) {
+                chromosome[item] = true;
+                total_weights = new_weights;
+            }
+        }
+    }
+}
+
+impl<T> Problem for MultidimensionalKnapsack<T>
+where
+    T: Num + Default + for<'a> std::iter::Sum<&'a T> + PartialOrd + NumCast + Bounded + Clone,
+{
+    type Fitness = T;
+    type Gene = bool;
+
+    fn chromosome_len(&self) -> usize {
+        self.prices.len()
+    }
+
+    fn fitness(&self, chromosome: &[bool]) -> T {
+        self.generation_fitness(chromosome, 0)
+    }
+
+    fn generation_fitness(&self, chromosome: &[bool], generation: usize) -> T {
+        let total_weights = self.total_weights(chromosome);
+        if fits(&total_weights, &self.capacities) {
+            return self.total_price(chromosome);
+        }
+
+        self.constraint_handling.penalize(
+            self.total_price(chromosome),
+            self.excess(&total_weights),
+            generation,
+        )
+    }
+
+    fn is_feasible(&self, chromosome: &[bool]) -> bool {
+        fits(&self.total_weights(chromosome), &self.capacities)
+    }
+
+    fn repair(&self, chromosome: &mut [bool]) {
+        self.repair_in_order(chromosome, &self.items_by_ratio());
+    }
+
+    fn repair_in_order(&self, chromosome: &mut [bool], items: &[usize]) {
+        let mut total_weights = self.total_weights(chromosome);
+
+        for &item in items.iter().rev() {
+            if fits(&total_weights, &self.capacities) {
+                break;
+            }
+
+            if chromosome[item] {
+                chromosome[item] = false;
+                total_weights
+                    .iter_mut()
+                    .zip(self.weights.iter())
+                    .for_each(|(weight, row)| *weight = weight.clone() - row[item].clone());
+            }
+        }
+
+        if self.repair_method != RepairMethod::DropWorstAndFill {
+            return;
+        }
+
+        self.fill(chromosome, total_weights, items);
+    }
+
+    fn greedy_order(&self) -> Option<Vec<usize>> {
+        Some(self.items_by_ratio())
+    }
+
+    fn fill_in_order(&self, order: &[usize]) -> Vec<bool> {
+        let mut chromosome = vec![false; self.chromosome_len()];
+        self.fill(
+            &mut chromosome,
+            vec![T::zero(); self.capacities.len()],
+            order,
+        );
+        chromosome
+    }
+
+    /// The tightest LP relaxation of a single constraint.
+    fn upper_bound(&self) -> Option<f64> {
+        let prices = to_f64s(&self.prices);
+        self.weights
+            .iter()
+            .zip(self.capacities.iter())
+            .filter_map(|(row, capacity)| {
+                fractional_bound(&prices, &to_f64s(row), capacity.to_f64()?)
+            })
+            .reduce(f64::min)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.weights.len() != self.capacities.len() {
+            return Err(Error::from(ConstraintsError(
+                self.weights.len(),
+                self.capacities.len(),
+            )));
+        }
+
+        if let Some((row_index, row)) = self
+            .weights
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != self.prices.len())
+        {
+            return Err(Error::from(RowDimensionsError(
+                row_index,
+                row.len(),
+                self.prices.len(),
+            )));
+        }
+
+        self.constraint_handling.validate()
+    }
+}
+
+fn fits<T>(total_weights: &[T], capacities: &[T]) -> bool
+where
+    T: PartialOrd,
+{
+    total_weights
+        .iter()
+        .zip(capacities.iter())
+        .all(|(weight, capacity)| weight <= capacity)
+}
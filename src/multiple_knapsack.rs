@@ -0,0 +1,204 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use num_traits::{Bounded, Num, NumCast, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::knapsack::{
+    fractional_bound, items_by_ratio, to_f64s, ConstraintHandling, DimensionsError, RepairMethod,
+};
+use crate::problem::Problem;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Multiple knapsack needs at least one knapsack capacity")]
+struct NoKnapsacksError;
+
+/// Items are assigned to one of `capacities.len()` knapsacks or not taken at all.
+/// Gene `0` means the item is not taken and gene `k` assigns it to knapsack `k - 1`,
+/// so every chromosome puts an item into at most one knapsack.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultipleKnapsack<T>
+where
+    T: Num,
+{
+    pub weights: Vec<T>,
+    pub prices: Vec<T>,
+    pub capacities: Vec<T>,
+    pub repair_method: RepairMethod,
+    pub constraint_handling: ConstraintHandling,
+}
+
+impl<T> MultipleKnapsack<T>
+where
+    T: Num + Clone,
+{
+    /// Total weight of items assigned to every knapsack.
+    pub fn loads(&self, chromosome: &[u32]) -> Vec<T> {
+        let mut loads = vec![T::zero(); self.capacities.len()];
+        chromosome
+            .iter()
+            .zip(self.weights.iter())
+            .filter(|(&knapsack, _)| knapsack > 0)
+            .for_each(|(&knapsack, weight)| {
+                let load = &mut loads[knapsack as usize - 1];
+                *load = load.clone() + weight.clone();
+            });
+
+        loads
+    }
+
+    pub fn total_price(&self, chromosome: &[u32]) -> T {
+        chromosome
+            .iter()
+            .zip(self.prices.iter())
+            .filter(|(&knapsack, _)| knapsack > 0)
+            .fold(T::zero(), |total, (_, price)| total + price.clone())
+    }
+}
+
+impl<T> MultipleKnapsack<T>
+where
+    T: Num + PartialOrd + ToPrimitive,
+{
+    fn excess(&self, loads: &[T]) -> f64 {
+        loads
+            .iter()
+            .zip(self.capacities.iter())
+            .filter(|(load, capacity)| load > capacity)
+            .map(|(load, capacity)| {
+                load.to_f64().unwrap_or(f64::INFINITY) - capacity.to_f64().unwrap_or(f64::NAN)
+            })
+            .sum()
+    }
+}
+
+impl<T> MultipleKnapsack<T>
+where
+    T: Num + PartialOrd + Clone,
+{
+    /// Puts items in given order that are not taken yet into the first knapsack they fit.
+    fn fill(&self, chromosome: &mut [u32], mut loads: Vec<T>, items: &[usize]) {
+        for &item in items {
+            if chromosome[item] != 0 {
+                continue;
+            }
+
+            let fitting_knapsack =
+                loads
+                    .iter()
+                    .zip(self.capacities.iter())
+                    .position(|(load, capacity)| {
+                        load.clone() + self.weights[item].clone() <= *capacity
+                    });
+
+            if let Some(knapsack) = fitting_knapsack {
+                chromosome[item] = knapsack as u32 + 1;
+                loads[knapsack] = loads[knapsack].clone() + self.weights[item].clone();
+            }
+        }
+    }
+}
+
+impl<T> Problem for MultipleKnapsack<T>
+where
+    T: Num + Default + PartialOrd + NumCast + Bounded + Clone,
+{
+    type Fitness = T;
+    type Gene = u32;
+
+    fn chromosome_len(&self) -> usize {
+        self.weights.len()
+    }
+
+    fn max_genes(&self) -> Vec<u32> {
+        vec![self.capacities.len() as u32; self.chromosome_len()]
+    }
+
+    fn fitness(&self, chromosome: &[u32]) -> T {
+        self.generation_fitness(chromosome, 0)
+    }
+
+    fn generation_fitness(&self, chromosome: &[u32], generation: usize) -> T {
+        let loads = self.loads(chromosome);
+        let excess = self.excess(&loads);
+        if excess <= 0.0 {
+            return self.total_price(chromosome);
+        }
+
+        self.constraint_handling
+            .penalize(self.total_price(chromosome), excess, generation)
+    }
+
+    fn is_feasible(&self, chromosome: &[u32]) -> bool {
+        self.loads(chromosome)
+            .iter()
+            .zip(self.capacities.iter())
+            .all(|(load, capacity)| load <= capacity)
+    }
+
+    fn repair(&self, chromosome: &mut [u32]) {
+        self.repair_in_order(chromosome, &items_by_ratio(&self.prices, &self.weights));
+    }
+
+    fn repair_in_order(&self, chromosome: &mut [u32], items: &[usize]) {
+        let mut loads = self.loads(chromosome);
+
+        for &item in items.iter().rev() {
+            if chromosome[item] == 0 {
+                continue;
+            }
+
+            let knapsack = chromosome[item] as usize - 1;
+            if loads[knapsack] > self.capacities[knapsack] {
+                chromosome[item] = 0;
+                loads[knapsack] = loads[knapsack].clone() - self.weights[item].clone();
+            }
+        }
+
+        if self.repair_method != RepairMethod::DropWorstAndFill {
+            return;
+        }
+
+        self.fill(chromosome, loads, items);
+    }
+
+    fn greedy_order(&self) -> Option<Vec<usize>> {
+        Some(items_by_ratio(&self.prices, &self.weights))
+    }
+
+    fn fill_in_order(&self, order: &[usize]) -> Vec<u32> {
+        let mut chromosome = vec![0; self.chromosome_len()];
+        self.fill(
+            &mut chromosome,
+            vec![T::zero(); self.capacities.len()],
+            order,
+        );
+        chromosome
+    }
+
+    /// All knapsacks relaxed into one with their total capacity.
+    fn upper_bound(&self) -> Option<f64> {
+        let capacity = self
+            .capacities
+            .iter()
+            .map(|capacity| capacity.to_f64().unwrap_or(f64::NAN))
+            .sum();
+
+        fractional_bound(&to_f64s(&self.prices), &to_f64s(&self.weights), capacity)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if self.weights.len() != self.prices.len() {
+            return Err(Error::from(DimensionsError(
+                self.weights.len(),
+                self.prices.len(),
+            )));
+        }
+
+        if self.capacities.is_empty() {
+            return Err(Error::from(NoKnapsacksError));
+        }
+
+        self.constraint_handling.validate()
+    }
+}
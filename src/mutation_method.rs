@@ -1,33 +1,125 @@
+use num_traits::PrimInt;
 use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use crate::gene::Gene;
 use crate::individual::Individual;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MutationMethod {
     BitFlip,
     Swap,
     Inversion,
 }
 
-pub fn mutation_method<T>(
-    individual: &Individual<T>,
-    method: &MutationMethod,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum IntegerMutationMethod {
+    /// Adds random value from range [-step, step] to the gene.
+    Creep {
+        step: usize,
+    },
+    /// Replaces the gene with a random value from its range.
+    RandomReset,
+    Swap,
+    Inversion,
+}
+
+pub trait Mutation<G> {
+    /// `max_genes` holds the largest allowed value of every gene.
+    fn mutate<T, R>(
+        &self,
+        individual: &Individual<T, G>,
+        max_genes: &[G],
+        mutation_rate: f64,
+        rng: &mut R,
+    ) -> Individual<T, G>
+    where
+        T: Clone,
+        R: Rng + ?Sized;
+}
+
+impl Mutation<bool> for MutationMethod {
+    fn mutate<T, R>(
+        &self,
+        individual: &Individual<T>,
+        _max_genes: &[bool],
+        mutation_rate: f64,
+        rng: &mut R,
+    ) -> Individual<T>
+    where
+        T: Clone,
+        R: Rng + ?Sized,
+    {
+        match self {
+            MutationMethod::BitFlip => bit_flip_mutation(individual, mutation_rate, rng),
+            MutationMethod::Swap => swap_mutation(individual, mutation_rate, rng),
+            MutationMethod::Inversion => inversion_mutation(individual, mutation_rate, rng),
+        }
+    }
+}
+
+impl<G> Mutation<G> for IntegerMutationMethod
+where
+    G: Gene + PrimInt,
+{
+    fn mutate<T, R>(
+        &self,
+        individual: &Individual<T, G>,
+        max_genes: &[G],
+        mutation_rate: f64,
+        rng: &mut R,
+    ) -> Individual<T, G>
+    where
+        T: Clone,
+        R: Rng + ?Sized,
+    {
+        let mut new_individual = match self {
+            IntegerMutationMethod::Creep { step } => {
+                creep_mutation(individual, max_genes, *step, mutation_rate, rng)
+            }
+            IntegerMutationMethod::RandomReset => {
+                random_reset_mutation(individual, max_genes, mutation_rate, rng)
+            }
+            IntegerMutationMethod::Swap => swap_mutation(individual, mutation_rate, rng),
+            IntegerMutationMethod::Inversion => inversion_mutation(individual, mutation_rate, rng),
+        };
+
+        // Swapped genes may not fit ranges of their new positions
+        new_individual
+            .chromosome
+            .iter_mut()
+            .zip(max_genes.iter())
+            .for_each(|(gene, max_gene)| *gene = (*gene).min(*max_gene));
+
+        new_individual
+    }
+}
+
+pub fn mutation_method<T, G, M, R>(
+    individual: &Individual<T, G>,
+    method: &M,
+    max_genes: &[G],
     mutation_rate: f64,
-) -> Individual<T>
+    rng: &mut R,
+) -> Individual<T, G>
 where
     T: Clone,
+    M: Mutation<G> + ?Sized,
+    R: Rng + ?Sized,
 {
-    match method {
-        MutationMethod::BitFlip => bit_flip_mutation(individual, mutation_rate),
-        MutationMethod::Swap => swap_mutation(individual, mutation_rate),
-        MutationMethod::Inversion => inversion_mutation(individual, mutation_rate),
-    }
+    method.mutate(individual, max_genes, mutation_rate, rng)
 }
 
-fn bit_flip_mutation<T>(individual: &Individual<T>, mutation_rate: f64) -> Individual<T>
+fn bit_flip_mutation<T, R>(
+    individual: &Individual<T>,
+    mutation_rate: f64,
+    rng: &mut R,
+) -> Individual<T>
 where
     T: Clone,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     let mut new_individual = individual.clone();
     new_individual.chromosome.iter_mut().for_each(|gene| {
         if rng.gen_bool(mutation_rate) {
@@ -38,11 +130,70 @@ where
     new_individual
 }
 
-fn swap_mutation<T>(individual: &Individual<T>, mutation_rate: f64) -> Individual<T>
+fn creep_mutation<T, G, R>(
+    individual: &Individual<T, G>,
+    max_genes: &[G],
+    step: usize,
+    mutation_rate: f64,
+    rng: &mut R,
+) -> Individual<T, G>
+where
+    T: Clone,
+    G: Gene + PrimInt,
+    R: Rng + ?Sized,
+{
+    let step = step as i128;
+    let mut new_individual = individual.clone();
+    new_individual
+        .chromosome
+        .iter_mut()
+        .zip(max_genes.iter())
+        .for_each(|(gene, max_gene)| {
+            if rng.gen_bool(mutation_rate) {
+                let max_gene = max_gene.to_i128().unwrap_or(i128::MAX);
+                let value = gene.to_i128().unwrap_or(0) + rng.gen_range(-step..=step);
+                *gene = G::from(value.clamp(0, max_gene)).unwrap_or(*gene);
+            }
+        });
+
+    new_individual
+}
+
+fn random_reset_mutation<T, G, R>(
+    individual: &Individual<T, G>,
+    max_genes: &[G],
+    mutation_rate: f64,
+    rng: &mut R,
+) -> Individual<T, G>
 where
     T: Clone,
+    G: Gene,
+    R: Rng + ?Sized,
+{
+    let mut new_individual = individual.clone();
+    new_individual
+        .chromosome
+        .iter_mut()
+        .zip(max_genes.iter())
+        .for_each(|(gene, max_gene)| {
+            if rng.gen_bool(mutation_rate) {
+                *gene = G::random(*max_gene, rng);
+            }
+        });
+
+    new_individual
+}
+
+fn swap_mutation<T, G, R>(
+    individual: &Individual<T, G>,
+    mutation_rate: f64,
+    rng: &mut R,
+) -> Individual<T, G>
+where
+    T: Clone,
+    G: Clone,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     let mut new_individual = individual.clone();
 
     if !rng.gen_bool(mutation_rate) {
@@ -59,11 +210,16 @@ where
     new_individual
 }
 
-fn inversion_mutation<T>(individual: &Individual<T>, mutation_rate: f64) -> Individual<T>
+fn inversion_mutation<T, G, R>(
+    individual: &Individual<T, G>,
+    mutation_rate: f64,
+    rng: &mut R,
+) -> Individual<T, G>
 where
     T: Clone,
+    G: Clone,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
     let mut new_individual = individual.clone();
 
     if !rng.gen_bool(mutation_rate) {
@@ -0,0 +1,405 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use anyhow::Error;
+use log::debug;
+use num_traits::ToPrimitive;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::crossover_method::{crossover_method, Crossover, CrossoverMethod};
+use crate::fitness::total_cmp;
+use crate::gene::Gene;
+use crate::genetic_algorithm::{
+    self, create_rng, generate_random_population, repair_order, validate_parameters,
+};
+use crate::individual::Individual;
+use crate::mutation_method::{mutation_method, Mutation, MutationMethod};
+use crate::problem::{Problem, RepairMode};
+
+/// Configuration of NSGA-II, every objective of `Problem::Fitness` is maximized.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Nsga2Data<P, C = CrossoverMethod, M = MutationMethod>
+where
+    P: Problem,
+{
+    pub problem: P,
+    pub population_size: usize,
+    pub generations: usize,
+    pub crossover_method: C,
+    pub crossover_rate: f64,
+    pub mutation_method: M,
+    pub mutation_rate: f64,
+    pub repair: Option<RepairMode>,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParetoResultData<T, G = bool> {
+    /// Distinct non-dominated individuals of the last population.
+    pub pareto_front: Vec<Individual<Vec<T>, G>>,
+}
+
+/// Scored individual with its feasibility.
+type Evaluated<T, G> = (Individual<Vec<T>, G>, bool);
+
+struct Ranked<T, G> {
+    individual: Individual<Vec<T>, G>,
+    feasible: bool,
+    rank: usize,
+    crowding_distance: f64,
+}
+
+pub fn nsga2<T, G, P, C, M>(data: &Nsga2Data<P, C, M>) -> Result<ParetoResultData<T, G>, Error>
+where
+    T: PartialOrd + ToPrimitive + Clone + std::fmt::Debug + Send,
+    G: Gene,
+    P: Problem<Fitness = Vec<T>, Gene = G> + Sync,
+    C: Crossover<G>,
+    M: Mutation<G>,
+{
+    data.problem.validate()?;
+    validate_parameters(
+        data.population_size,
+        data.crossover_rate,
+        data.mutation_rate,
+    )?;
+
+    let mut rng = create_rng(data.seed);
+
+    let max_genes = data.problem.max_genes();
+    let repair_order = repair_order(&data.problem, data.repair);
+    let population =
+        generate_random_population::<Vec<T>, _, _>(&max_genes, data.population_size, &mut rng);
+    let mut population = select_survivors(
+        evaluate_population(data, &repair_order, population, 0),
+        data.population_size,
+    );
+
+    for generation in 1..data.generations {
+        let offspring = generate_offspring(data, &population, &max_genes, &mut rng);
+        let mut combined = evaluate_population(data, &repair_order, offspring, generation);
+        combined.extend(
+            population
+                .into_iter()
+                .map(|ranked| (ranked.individual, ranked.feasible)),
+        );
+
+        population = select_survivors(combined, data.population_size);
+        debug!(
+            "Generation {generation}: {} non-dominated individuals",
+            population.iter().filter(|ranked| ranked.rank == 0).count()
+        );
+    }
+
+    let mut chromosomes = HashSet::new();
+    let pareto_front = population
+        .into_iter()
+        .filter(|ranked| ranked.rank == 0)
+        .map(|ranked| ranked.individual)
+        .filter(|individual| chromosomes.insert(individual.chromosome.clone()))
+        .collect();
+
+    Ok(ParetoResultData { pareto_front })
+}
+
+fn evaluate_population<T, G, P, C, M>(
+    data: &Nsga2Data<P, C, M>,
+    repair_order: &[usize],
+    mut population: Vec<Individual<Vec<T>, G>>,
+    generation: usize,
+) -> Vec<Evaluated<T, G>>
+where
+    T: Send,
+    G: Gene,
+    P: Problem<Fitness = Vec<T>, Gene = G> + Sync,
+{
+    genetic_algorithm::evaluate_population(
+        &data.problem,
+        data.repair,
+        repair_order,
+        &mut population,
+        generation,
+    );
+
+    population
+        .into_iter()
+        .map(|individual| {
+            let feasible = match data.repair {
+                Some(RepairMode::Baldwinian) => {
+                    let mut repaired = individual.chromosome.clone();
+                    data.problem.repair_in_order(&mut repaired, repair_order);
+                    data.problem.is_feasible(&repaired)
+                }
+                _ => data.problem.is_feasible(&individual.chromosome),
+            };
+            (individual, feasible)
+        })
+        .collect()
+}
+
+/// Keeps `size` individuals filling the population by fronts, the last front is cut by crowding distance.
+fn select_survivors<T, G>(population: Vec<Evaluated<T, G>>, size: usize) -> Vec<Ranked<T, G>>
+where
+    T: PartialOrd + ToPrimitive,
+{
+    let fronts = non_dominated_sort(&population);
+
+    let mut survivors: Vec<(usize, usize, f64)> = Vec::with_capacity(size);
+    for (rank, front) in fronts.iter().enumerate() {
+        if survivors.len() >= size {
+            break;
+        }
+
+        let distances = crowding_distances(&population, front);
+        let mut front: Vec<(usize, usize, f64)> = front
+            .iter()
+            .zip(distances)
+            .map(|(&index, distance)| (index, rank, distance))
+            .collect();
+
+        if survivors.len() + front.len() > size {
+            front.sort_by(|a, b| b.2.total_cmp(&a.2));
+            front.truncate(size - survivors.len());
+        }
+        survivors.append(&mut front);
+    }
+
+    let mut population: Vec<Option<Evaluated<T, G>>> = population.into_iter().map(Some).collect();
+    survivors
+        .into_iter()
+        .map(|(index, rank, crowding_distance)| {
+            let (individual, feasible) = population[index].take().unwrap();
+            Ranked {
+                individual,
+                feasible,
+                rank,
+                crowding_distance,
+            }
+        })
+        .collect()
+}
+
+/// Indices of individuals split into fronts, the first front is not dominated by any individual.
+fn non_dominated_sort<T, G>(population: &[Evaluated<T, G>]) -> Vec<Vec<usize>>
+where
+    T: PartialOrd,
+{
+    let mut dominated_by: Vec<Vec<usize>> = vec![Vec::new(); population.len()];
+    let mut domination_count = vec![0; population.len()];
+
+    for a in 0..population.len() {
+        for b in (a + 1)..population.len() {
+            if dominates(&population[a], &population[b]) {
+                dominated_by[a].push(b);
+                domination_count[b] += 1;
+            } else if dominates(&population[b], &population[a]) {
+                dominated_by[b].push(a);
+                domination_count[a] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..population.len())
+        .filter(|&index| domination_count[index] == 0)
+        .collect();
+
+    while !front.is_empty() {
+        let mut next_front = Vec::new();
+        for &index in front.iter() {
+            for &dominated in dominated_by[index].iter() {
+                domination_count[dominated] -= 1;
+                if domination_count[dominated] == 0 {
+                    next_front.push(dominated);
+                }
+            }
+        }
+
+        fronts.push(front);
+        front = next_front;
+    }
+
+    fronts
+}
+
+/// Feasible individual dominates infeasible one, otherwise `a` dominates `b` when it is not worse
+/// in any objective and better in at least one.
+fn dominates<T, G>(a: &Evaluated<T, G>, b: &Evaluated<T, G>) -> bool
+where
+    T: PartialOrd,
+{
+    if a.1 != b.1 {
+        return a.1;
+    }
+
+    let mut is_better = false;
+    for (a, b) in a.0.fitness_score.iter().zip(b.0.fitness_score.iter()) {
+        match total_cmp(a, b) {
+            Ordering::Less => return false,
+            Ordering::Greater => is_better = true,
+            Ordering::Equal => {}
+        }
+    }
+
+    is_better
+}
+
+fn crowding_distances<T, G>(population: &[Evaluated<T, G>], front: &[usize]) -> Vec<f64>
+where
+    T: ToPrimitive,
+{
+    let mut distances = vec![0.0; front.len()];
+    let n_objectives = front
+        .first()
+        .map_or(0, |&index| population[index].0.fitness_score.len());
+
+    for objective in 0..n_objectives {
+        let values: Vec<f64> = front
+            .iter()
+            .map(|&index| {
+                population[index].0.fitness_score[objective]
+                    .to_f64()
+                    .unwrap_or(f64::NAN)
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..front.len()).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+        let (first, last) = (order[0], order[order.len() - 1]);
+        distances[first] = f64::INFINITY;
+        distances[last] = f64::INFINITY;
+
+        let range = values[last] - values[first];
+        if range <= 0.0 {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            distances[window[1]] += (values[window[2]] - values[window[0]]) / range;
+        }
+    }
+
+    distances
+}
+
+fn generate_offspring<T, G, P, C, M, R>(
+    data: &Nsga2Data<P, C, M>,
+    population: &[Ranked<T, G>],
+    max_genes: &[G],
+    rng: &mut R,
+) -> Vec<Individual<Vec<T>, G>>
+where
+    T: Clone,
+    G: Gene,
+    P: Problem<Fitness = Vec<T>, Gene = G>,
+    C: Crossover<G>,
+    M: Mutation<G>,
+    R: Rng + ?Sized,
+{
+    let mut offspring = Vec::with_capacity(data.population_size);
+
+    while offspring.len() < data.population_size {
+        let parents = (
+            crowded_tournament(population, rng),
+            crowded_tournament(population, rng),
+        );
+
+        let children = crossover_method(parents, &data.crossover_method, data.crossover_rate, rng);
+        offspring.push(mutation_method(
+            &children.0,
+            &data.mutation_method,
+            max_genes,
+            data.mutation_rate,
+            rng,
+        ));
+        offspring.push(mutation_method(
+            &children.1,
+            &data.mutation_method,
+            max_genes,
+            data.mutation_rate,
+            rng,
+        ));
+    }
+
+    offspring
+}
+
+/// Binary tournament preferring lower rank, then larger crowding distance.
+fn crowded_tournament<'a, T, G, R>(
+    population: &'a [Ranked<T, G>],
+    rng: &mut R,
+) -> &'a Individual<Vec<T>, G>
+where
+    R: Rng + ?Sized,
+{
+    let a = &population[rng.gen_range(0..population.len())];
+    let b = &population[rng.gen_range(0..population.len())];
+
+    let a_wins = match a.rank.cmp(&b.rank) {
+        Ordering::Less => true,
+        Ordering::Greater => false,
+        Ordering::Equal => a.crowding_distance >= b.crowding_distance,
+    };
+
+    if a_wins {
+        &a.individual
+    } else {
+        &b.individual
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluated(objectives: Vec<i32>, is_feasible: bool) -> Evaluated<i32, bool> {
+        (
+            Individual {
+                chromosome: Vec::new(),
+                fitness_score: objectives,
+            },
+            is_feasible,
+        )
+    }
+
+    fn population() -> Vec<Evaluated<i32, bool>> {
+        vec![
+            evaluated(vec![4, 1], true),
+            evaluated(vec![1, 4], true),
+            evaluated(vec![3, 3], true),
+            evaluated(vec![2, 2], true),
+            evaluated(vec![1, 1], true),
+            evaluated(vec![5, 5], false),
+        ]
+    }
+
+    #[test]
+    fn sorts_into_fronts() {
+        assert_eq!(
+            non_dominated_sort(&population()),
+            vec![vec![0, 1, 2], vec![3], vec![4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn boundary_points_have_infinite_distance() {
+        assert_eq!(
+            crowding_distances(&population(), &[0, 1, 2]),
+            vec![f64::INFINITY, f64::INFINITY, 2.0]
+        );
+    }
+
+    #[test]
+    fn feasible_dominates_infeasible() {
+        let population = population();
+        assert!(dominates(&population[4], &population[5]));
+        assert!(!dominates(&population[5], &population[4]));
+        assert!(dominates(&population[2], &population[3]));
+        assert!(!dominates(&population[0], &population[1]));
+        assert!(!dominates(&population[0], &population[0]));
+    }
+}
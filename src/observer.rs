@@ -0,0 +1,26 @@
+use std::ops::ControlFlow;
+
+use crate::individual::Individual;
+use crate::statistics::GenerationStatistics;
+
+pub struct GenerationReport<'a, T, G = bool> {
+    pub generation: usize,
+    pub population: &'a [Individual<T, G>],
+    pub best_individual: &'a Individual<T, G>,
+    pub current_best_individual: &'a Individual<T, G>,
+    pub statistics: &'a GenerationStatistics<T>,
+}
+
+pub trait Observer<T, G = bool> {
+    /// Called after every evaluated generation, returning `ControlFlow::Break` stops the run.
+    fn on_generation(&mut self, report: &GenerationReport<T, G>) -> ControlFlow<()>;
+}
+
+impl<T, G, F> Observer<T, G> for F
+where
+    F: FnMut(&GenerationReport<T, G>) -> ControlFlow<()>,
+{
+    fn on_generation(&mut self, report: &GenerationReport<T, G>) -> ControlFlow<()> {
+        self(report)
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::gene::Gene;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RepairMode {
+    /// Repaired chromosome replaces the original one.
+    Lamarckian,
+    /// Repaired chromosome is only used for scoring, the original one is kept.
+    Baldwinian,
+}
+
+pub trait Problem {
+    type Fitness;
+    type Gene: Gene;
+
+    fn chromosome_len(&self) -> usize;
+
+    /// Largest allowed value of every gene.
+    fn max_genes(&self) -> Vec<Self::Gene> {
+        vec![Self::Gene::MAX; self.chromosome_len()]
+    }
+
+    fn fitness(&self, chromosome: &[Self::Gene]) -> Self::Fitness;
+
+    /// Fitness in given generation, allows the score to change during the run (e.g. adaptive penalties).
+    fn generation_fitness(&self, chromosome: &[Self::Gene], _generation: usize) -> Self::Fitness {
+        self.fitness(chromosome)
+    }
+
+    fn is_feasible(&self, _chromosome: &[Self::Gene]) -> bool {
+        true
+    }
+
+    /// Modifies infeasible chromosome so it becomes feasible, used when `repair` of `GeneticAlgorithmData` is set.
+    fn repair(&self, _chromosome: &mut [Self::Gene]) {}
+
+    /// Same as `repair` with `greedy_order` computed once per run (empty when there is none),
+    /// so problems repairing genes in that order do not sort them for every chromosome.
+    fn repair_in_order(&self, chromosome: &mut [Self::Gene], _order: &[usize]) {
+        self.repair(chromosome);
+    }
+
+    /// Gene positions from the most to the least promising one (e.g. items by price/weight ratio), used by greedy initialization.
+    fn greedy_order(&self) -> Option<Vec<usize>> {
+        None
+    }
+
+    /// Chromosome built by setting genes in `order` to their largest value (`max_genes`) when it stays feasible,
+    /// other genes keep the default value. Used by initialization, problems with integer genes may override it
+    /// to try smaller values.
+    fn fill_in_order(&self, order: &[usize]) -> Vec<Self::Gene> {
+        let max_genes = self.max_genes();
+        let mut chromosome = vec![Self::Gene::default(); self.chromosome_len()];
+
+        for &gene in order {
+            chromosome[gene] = max_genes[gene];
+            if !self.is_feasible(&chromosome) {
+                chromosome[gene] = Self::Gene::default();
+            }
+        }
+
+        chromosome
+    }
+
+    /// Upper bound of the best fitness (e.g. optimum of the LP relaxation), used to report the optimality gap.
+    fn upper_bound(&self) -> Option<f64> {
+        None
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
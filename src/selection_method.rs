@@ -6,6 +6,8 @@ use num_traits::Num;
 use rand::distributions::uniform::SampleUniform;
 use rand::seq::SliceRandom;
 use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::iter::Sum;
 use std::ops::AddAssign;
 
@@ -23,6 +25,7 @@ struct RouletteError;
 #[display(fmt = "Secondary selection cannot be Elitism")]
 struct InvalidSecondarySelectionError;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SelectionMethod {
     Tournament {
         size: usize,
@@ -34,62 +37,112 @@ pub enum SelectionMethod {
     Roulette,
 }
 
-pub fn selection_method<T>(
-    population: &Vec<Individual<T>>,
-    method: &SelectionMethod,
-) -> Result<Individual<T>, Error>
+pub trait Selection<T> {
+    fn select<G, R>(
+        &self,
+        population: &[Individual<T, G>],
+        rng: &mut R,
+    ) -> Result<Individual<T, G>, Error>
+    where
+        G: Clone,
+        R: Rng + ?Sized;
+
+    /// Number of best individuals copied unchanged to the next generation.
+    fn n_elites(&self) -> usize {
+        0
+    }
+}
+
+impl<T> Selection<T> for SelectionMethod
 where
-    T: Num + Ord + Clone + Sum + AddAssign + SampleUniform,
+    T: Num + PartialOrd + Clone + Sum + AddAssign + SampleUniform,
 {
-    match method {
-        SelectionMethod::Tournament { size } => tournament_selection(population, *size),
-        SelectionMethod::Roulette => roulette_selection(population),
-        SelectionMethod::Elitism {
-            n_elites: _,
-            secondary_selection,
-        } => handle_secondary_method(population, secondary_selection),
+    fn select<G, R>(
+        &self,
+        population: &[Individual<T, G>],
+        rng: &mut R,
+    ) -> Result<Individual<T, G>, Error>
+    where
+        G: Clone,
+        R: Rng + ?Sized,
+    {
+        match self {
+            SelectionMethod::Tournament { size } => tournament_selection(population, *size, rng),
+            SelectionMethod::Roulette => roulette_selection(population, rng),
+            SelectionMethod::Elitism {
+                n_elites: _,
+                secondary_selection,
+            } => handle_secondary_method(population, secondary_selection, rng),
+        }
+    }
+
+    fn n_elites(&self) -> usize {
+        match self {
+            SelectionMethod::Elitism { n_elites, .. } => *n_elites,
+            _ => 0,
+        }
     }
 }
 
-pub fn select_elites<T>(
-    population: &Vec<Individual<T>>,
+pub fn selection_method<T, G, S, R>(
+    population: &[Individual<T, G>],
+    method: &S,
+    rng: &mut R,
+) -> Result<Individual<T, G>, Error>
+where
+    G: Clone,
+    S: Selection<T> + ?Sized,
+    R: Rng + ?Sized,
+{
+    method.select(population, rng)
+}
+
+pub fn select_elites<T, G>(
+    population: &[Individual<T, G>],
     n_elites: usize,
-) -> Result<Vec<Individual<T>>, Error>
+) -> Result<Vec<Individual<T, G>>, Error>
 where
-    T: Num + Ord + Clone,
+    T: Num + PartialOrd + Clone,
+    G: Clone,
 {
     if population.len() < n_elites {
         return Err(Error::from(PopulationSizeError(population.len(), n_elites)));
     }
 
-    let mut cloned_population: Vec<Individual<T>> = population.to_vec();
+    let mut cloned_population: Vec<Individual<T, G>> = population.to_vec();
     cloned_population.sort();
     cloned_population.reverse();
-    let elites: Vec<Individual<T>> = cloned_population[..n_elites].to_vec();
+    let elites: Vec<Individual<T, G>> = cloned_population[..n_elites].to_vec();
 
     Ok(elites)
 }
 
-fn handle_secondary_method<T>(
-    population: &Vec<Individual<T>>,
+fn handle_secondary_method<T, G, R>(
+    population: &[Individual<T, G>],
     secondary_method: &SelectionMethod,
-) -> Result<Individual<T>, Error>
+    rng: &mut R,
+) -> Result<Individual<T, G>, Error>
 where
-    T: Num + Ord + Clone + Sum + AddAssign + SampleUniform,
+    T: Num + PartialOrd + Clone + Sum + AddAssign + SampleUniform,
+    G: Clone,
+    R: Rng + ?Sized,
 {
     if let SelectionMethod::Elitism { .. } = *secondary_method {
         return Err(Error::from(InvalidSecondarySelectionError));
     }
 
-    selection_method(population, secondary_method)
+    selection_method(population, secondary_method, rng)
 }
 
-fn tournament_selection<T>(
-    population: &Vec<Individual<T>>,
+fn tournament_selection<T, G, R>(
+    population: &[Individual<T, G>],
     tournament_size: usize,
-) -> Result<Individual<T>, Error>
+    rng: &mut R,
+) -> Result<Individual<T, G>, Error>
 where
-    T: Num + Ord + Clone,
+    T: Num + PartialOrd + Clone,
+    G: Clone,
+    R: Rng + ?Sized,
 {
     if population.len() < tournament_size {
         return Err(Error::from(PopulationSizeError(
@@ -98,30 +151,66 @@ where
         )));
     }
 
-    let mut rng = rand::thread_rng();
-
     Ok(population
-        .choose_multiple(&mut rng, tournament_size)
+        .choose_multiple(rng, tournament_size)
         .cloned()
         .max()
         .unwrap())
 }
 
-fn roulette_selection<T>(population: &[Individual<T>]) -> Result<Individual<T>, Error>
+fn roulette_selection<T, G, R>(
+    population: &[Individual<T, G>],
+    rng: &mut R,
+) -> Result<Individual<T, G>, Error>
 where
-    T: Num + Ord + Clone + Sum + AddAssign + SampleUniform,
+    T: Num + PartialOrd + Clone + Sum + AddAssign + SampleUniform,
+    G: Clone,
+    R: Rng + ?Sized,
 {
-    let mut rng = rand::thread_rng();
-    let total_fitness = population
+    if population.is_empty() {
+        return Err(Error::from(RouletteError));
+    }
+
+    // Non-finite scores (NaN, infinities) get zero chance, `x - x` is zero only for finite values
+    let is_finite = |score: &T| score.clone() - score.clone() == T::zero();
+    let min_fitness = population
+        .iter()
+        .map(|individual| &individual.fitness_score)
+        .filter(|score| is_finite(score))
+        .fold(
+            T::zero(),
+            |min, score| {
+                if *score < min {
+                    score.clone()
+                } else {
+                    min
+                }
+            },
+        );
+    // Negative scores (e.g. penalized individuals) are shifted so the worst one gets zero chance
+    let offset = T::zero() - min_fitness;
+
+    let weights: Vec<T> = population
         .iter()
-        .map(|individual| individual.fitness_score.clone())
-        .sum();
+        .map(|individual| {
+            if is_finite(&individual.fitness_score) {
+                individual.fitness_score.clone() + offset.clone()
+            } else {
+                T::zero()
+            }
+        })
+        .collect();
+    let total_fitness: T = weights.iter().cloned().sum();
+    if !is_finite(&total_fitness) || total_fitness <= T::zero() {
+        return Ok(population.choose(rng).ok_or(RouletteError)?.clone());
+    }
+
     let random_number = rng.gen_range(T::zero()..total_fitness);
 
     let mut cumulative_fitness = T::zero();
-    for individual in population.iter() {
-        cumulative_fitness += individual.fitness_score.clone();
-        if cumulative_fitness >= random_number {
+    for (individual, weight) in population.iter().zip(weights) {
+        cumulative_fitness += weight;
+        if cumulative_fitness > random_number {
             return Ok(individual.clone());
         }
     }
@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use num_traits::{Num, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::individual::Individual;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GenerationStatistics<T> {
+    pub best_score: T,
+    pub worst_score: T,
+    pub mean_score: f64,
+    pub median_score: f64,
+    pub standard_deviation: f64,
+    pub feasible_individuals: usize,
+    pub distinct_chromosomes: usize,
+    /// Total number of fitness evaluations done until this generation.
+    pub evaluations: usize,
+    /// Gap between the best score found until this generation and the upper bound of the problem.
+    pub optimality_gap: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct History<T> {
+    pub generations: Vec<GenerationStatistics<T>>,
+}
+
+impl<T> History<T>
+where
+    T: Clone,
+{
+    pub fn len(&self) -> usize {
+        self.generations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&GenerationStatistics<T>> {
+        self.generations.last()
+    }
+
+    pub fn best_scores(&self) -> Vec<T> {
+        self.generations
+            .iter()
+            .map(|statistics| statistics.best_score.clone())
+            .collect()
+    }
+
+    pub fn mean_scores(&self) -> Vec<f64> {
+        self.generations
+            .iter()
+            .map(|statistics| statistics.mean_score)
+            .collect()
+    }
+}
+
+impl<T> Default for History<T> {
+    fn default() -> History<T> {
+        History {
+            generations: Vec::new(),
+        }
+    }
+}
+
+pub(crate) fn calculate_statistics<T, G>(
+    population: &[Individual<T, G>],
+    feasible_individuals: usize,
+    evaluations: usize,
+    optimality_gap: Option<f64>,
+) -> GenerationStatistics<T>
+where
+    T: Num + PartialOrd + Clone + ToPrimitive,
+    G: Eq + Hash,
+{
+    let mut scores: Vec<f64> = population
+        .iter()
+        .map(|individual| individual.fitness_score.to_f64().unwrap_or(f64::NAN))
+        .collect();
+    scores.sort_by(f64::total_cmp);
+
+    let mean_score = scores.iter().sum::<f64>() / scores.len() as f64;
+    let variance = scores
+        .iter()
+        .map(|score| (score - mean_score).powi(2))
+        .sum::<f64>()
+        / scores.len() as f64;
+
+    let middle = scores.len() / 2;
+    let median_score = if scores.len().is_multiple_of(2) {
+        (scores[middle - 1] + scores[middle]) / 2.0
+    } else {
+        scores[middle]
+    };
+
+    let distinct_chromosomes = population
+        .iter()
+        .map(|individual| &individual.chromosome)
+        .collect::<HashSet<_>>()
+        .len();
+
+    GenerationStatistics {
+        best_score: population.iter().max().unwrap().fitness_score.clone(),
+        worst_score: population.iter().min().unwrap().fitness_score.clone(),
+        mean_score,
+        median_score,
+        standard_deviation: variance.sqrt(),
+        feasible_individuals,
+        distinct_chromosomes,
+        evaluations,
+        optimality_gap,
+    }
+}
+
+/// Gap relative to the upper bound, zero when the score reaches it.
+pub(crate) fn relative_gap(score: f64, upper_bound: f64) -> f64 {
+    (upper_bound - score) / upper_bound.abs().max(f64::MIN_POSITIVE)
+}
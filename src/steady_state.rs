@@ -0,0 +1,75 @@
+use anyhow::Error;
+use derive_more::{Display, Error};
+use rand::seq::index::sample;
+use rand::Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::individual::Individual;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Number of offspring ({_0}) must be in range of [1 - population_size ({_1})]")]
+struct OffspringError(#[error(not(source))] usize, usize);
+
+#[derive(Debug, Display, Error)]
+#[display(
+    fmt = "Replacement tournament size ({_0}) must be in range of [1 - population_size ({_1})]"
+)]
+struct ReplacementTournamentError(#[error(not(source))] usize, usize);
+
+/// Steady-state mode, every step breeds only `offspring` children which replace members of the current population.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SteadyState {
+    pub offspring: usize,
+    pub replacement: Replacement,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Replacement {
+    /// Child replaces the worst individual of the population.
+    Worst,
+    /// Child replaces the worst of `size` randomly chosen individuals.
+    Tournament { size: usize },
+}
+
+impl SteadyState {
+    pub(crate) fn validate(&self, population_size: usize) -> Result<(), Error> {
+        if self.offspring == 0 || self.offspring > population_size {
+            return Err(Error::from(OffspringError(self.offspring, population_size)));
+        }
+
+        if let Replacement::Tournament { size } = self.replacement {
+            if size == 0 || size > population_size {
+                return Err(Error::from(ReplacementTournamentError(
+                    size,
+                    population_size,
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Index of the individual replaced by the next child.
+    pub(crate) fn select_replaced<T, G, R>(
+        &self,
+        population: &[Individual<T, G>],
+        rng: &mut R,
+    ) -> usize
+    where
+        T: PartialOrd,
+        R: Rng + ?Sized,
+    {
+        match self.replacement {
+            Replacement::Worst => (0..population.len())
+                .min_by(|&a, &b| population[a].cmp(&population[b]))
+                .unwrap(),
+            Replacement::Tournament { size } => sample(rng, population.len(), size)
+                .into_iter()
+                .min_by(|&a, &b| population[a].cmp(&population[b]))
+                .unwrap(),
+        }
+    }
+}
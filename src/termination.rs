@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use anyhow::Error;
+use derive_more::{Display, Error};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::individual::Individual;
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Convergence ratio ({_0}) is not in range of [0 - 1]")]
+struct ConvergenceRatioError(#[error(not(source))] f64);
+
+#[derive(Debug, Display, Error)]
+#[display(fmt = "Any and All termination criteria cannot be empty")]
+struct EmptyCriteriaError;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TerminationCriterion<T> {
+    TimeLimit(Duration),
+    FitnessEvaluations(usize),
+    /// Score reached by a feasible best individual.
+    TargetScore(T),
+    /// Number of generations without improvement of the best individual.
+    Stagnation(usize),
+    /// Fraction of the population sharing the chromosome of the generation's best individual.
+    Convergence(f64),
+    Any(Vec<TerminationCriterion<T>>),
+    All(Vec<TerminationCriterion<T>>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TerminationReason {
+    Generations,
+    TimeLimit,
+    FitnessEvaluations,
+    TargetScore,
+    Stagnation,
+    Convergence,
+    Observer,
+    All(Vec<TerminationReason>),
+}
+
+pub(crate) struct RunProgress<'a, T, G> {
+    pub elapsed: Duration,
+    pub evaluations: usize,
+    pub stagnant_generations: usize,
+    pub best_individual: &'a Individual<T, G>,
+    pub is_best_feasible: bool,
+    pub current_best_individual: &'a Individual<T, G>,
+    pub population: &'a [Individual<T, G>],
+}
+
+impl<T> TerminationCriterion<T>
+where
+    T: PartialOrd,
+{
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        match self {
+            TerminationCriterion::Convergence(ratio) if !(0.0..=1.0).contains(ratio) => {
+                Err(Error::from(ConvergenceRatioError(*ratio)))
+            }
+            TerminationCriterion::Any(criteria) | TerminationCriterion::All(criteria) => {
+                if criteria.is_empty() {
+                    return Err(Error::from(EmptyCriteriaError));
+                }
+
+                criteria
+                    .iter()
+                    .try_for_each(|criterion| criterion.validate())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check<G>(&self, progress: &RunProgress<T, G>) -> Option<TerminationReason>
+    where
+        G: PartialEq,
+    {
+        match self {
+            TerminationCriterion::TimeLimit(limit) => {
+                (progress.elapsed >= *limit).then_some(TerminationReason::TimeLimit)
+            }
+            TerminationCriterion::FitnessEvaluations(max_evaluations) => (progress.evaluations
+                >= *max_evaluations)
+                .then_some(TerminationReason::FitnessEvaluations),
+            TerminationCriterion::TargetScore(target) => (progress.is_best_feasible
+                && progress.best_individual.fitness_score >= *target)
+                .then_some(TerminationReason::TargetScore),
+            TerminationCriterion::Stagnation(generations) => (progress.stagnant_generations
+                >= *generations)
+                .then_some(TerminationReason::Stagnation),
+            TerminationCriterion::Convergence(ratio) => {
+                let converged = progress
+                    .population
+                    .iter()
+                    .filter(|individual| {
+                        individual.chromosome == progress.current_best_individual.chromosome
+                    })
+                    .count();
+
+                (converged as f64 >= ratio * progress.population.len() as f64)
+                    .then_some(TerminationReason::Convergence)
+            }
+            TerminationCriterion::Any(criteria) => criteria
+                .iter()
+                .find_map(|criterion| criterion.check(progress)),
+            TerminationCriterion::All(criteria) => criteria
+                .iter()
+                .map(|criterion| criterion.check(progress))
+                .collect::<Option<Vec<_>>>()
+                .map(TerminationReason::All),
+        }
+    }
+}
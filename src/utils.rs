@@ -1,7 +1,7 @@
 use anyhow::Error;
-use num_traits::Num;
+use derive_more::{Display, Error};
+use num_traits::{Num, ToPrimitive};
 use plotters::prelude::*;
-use std::convert::From;
 
 use crate::genetic_algorithm::GeneticAlgorithmResultData;
 
@@ -12,6 +12,10 @@ const Y_DESC: &str = "Score";
 const LEGEND_LABEL: &str = "Score for generation";
 const OPTIMAL_LEGEND_LABEL: &str = "Optimal score";
 
+#[derive(Debug, Display, Error)]
+#[display(fmt = "History has no finite scores to plot")]
+struct EmptyHistoryError;
+
 pub struct GraphData<'a> {
     pub out_file: &'a str,
     pub graph_size: (u32, u32),
@@ -43,16 +47,28 @@ pub fn plot_graph<T>(
     graph_data: &GraphData,
 ) -> Result<(), Error>
 where
-    T: Num + Clone + Ord + Default,
-    f64: From<T>,
+    T: Num + Clone + ToPrimitive,
 {
-    let min_y = f32::min(
-        0.0,
-        to_f32(data.score_per_generation.iter().min().unwrap().clone()),
-    );
-    let max_y = graph_data
-        .y_max_value
-        .unwrap_or(1.2 * to_f32(data.score_per_generation.iter().max().unwrap().clone()));
+    let best_scores: Vec<f32> = data.history.best_scores().into_iter().map(to_f32).collect();
+    let finite_scores = || {
+        best_scores
+            .iter()
+            .copied()
+            .filter(|score| score.is_finite())
+    };
+    if finite_scores().next().is_none() {
+        return Err(Error::from(EmptyHistoryError));
+    }
+
+    // Axis covers zero and all scores with a margin above the highest one
+    let min_y = finite_scores().fold(0.0, f32::min);
+    let highest_y = finite_scores().fold(0.0, f32::max);
+    let range = highest_y - min_y;
+    let max_y = graph_data.y_max_value.unwrap_or(if range > 0.0 {
+        highest_y + 0.2 * range
+    } else {
+        1.0
+    });
 
     let root = BitMapBackend::new(&graph_data.out_file, graph_data.graph_size).into_drawing_area();
     root.fill(&WHITE)?;
@@ -64,7 +80,7 @@ where
         .margin(graph_data.margin)
         .x_label_area_size(graph_data.x_label_area_size)
         .y_label_area_size(graph_data.y_label_area_size)
-        .build_cartesian_2d(0.0f32..data.score_per_generation.len() as f32, min_y..max_y)?;
+        .build_cartesian_2d(0.0f32..best_scores.len() as f32, min_y..max_y)?;
 
     chart
         .configure_mesh()
@@ -75,7 +91,7 @@ where
     if let Some(optimal_score) = graph_data.optimal_value_line {
         chart
             .draw_series(LineSeries::new(
-                (0..data.score_per_generation.len()).map(|x| (x as f32, optimal_score)),
+                (0..best_scores.len()).map(|x| (x as f32, optimal_score)),
                 &GREEN,
             ))?
             .label(OPTIMAL_LEGEND_LABEL)
@@ -84,10 +100,7 @@ where
 
     chart
         .draw_series(LineSeries::new(
-            data.score_per_generation
-                .iter()
-                .enumerate()
-                .map(|(x, y)| (x as f32, to_f32((*y).clone()))),
+            best_scores.iter().enumerate().map(|(x, y)| (x as f32, *y)),
             &RED,
         ))?
         .label(LEGEND_LABEL)
@@ -106,7 +119,7 @@ where
 
 fn to_f32<T>(value: T) -> f32
 where
-    f64: From<T>,
+    T: ToPrimitive,
 {
-    f64::from(value) as f32
+    value.to_f32().unwrap_or(f32::NAN)
 }